@@ -0,0 +1,4 @@
+//! The solutions of Advent of Code 2023: a public module `dayN` for every day with its
+//! [`Solution`](aoc_common::Solution) `DayN`, and the [`DAYS`] table of all of them.
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
@@ -1,20 +1,34 @@
 use std::collections::HashMap;
 
-pub fn day1(data: &str, p1: bool) -> i64 {
-    let (mut l_nums, mut r_nums): (Vec<_>, Vec<_>) = data
-        .lines()
-        .filter_map(|l| {
-            let (lhs, rhs) = l.split_once(char::is_whitespace)?;
+use aoc_common::{parse, Answer, Solution};
+
+#[derive(Clone)]
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = (Vec<u32>, Vec<u32>);
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let mut lhs_list = vec![];
+        let mut rhs_list = vec![];
+        for l in data.lines() {
+            let Some((lhs, rhs)) = l.split_once(char::is_whitespace) else {
+                continue;
+            };
             let lhs = lhs.trim();
             let rhs = rhs.trim();
             if lhs.is_empty() || rhs.is_empty() {
-                return None;
+                continue;
             }
-            Some((lhs.parse::<u32>().unwrap(), rhs.parse::<u32>().unwrap()))
-        })
-        .unzip();
+            lhs_list.push(parse::from_str::<u32>(data, lhs)?);
+            rhs_list.push(parse::from_str::<u32>(data, rhs)?);
+        }
+        Ok((lhs_list, rhs_list))
+    }
 
-    if p1 {
+    fn part1(&self, (l_nums, r_nums): &Self::Input) -> Answer {
+        let mut l_nums = l_nums.clone();
+        let mut r_nums = r_nums.clone();
         l_nums.sort_unstable();
         r_nums.sort_unstable();
 
@@ -24,19 +38,21 @@ pub fn day1(data: &str, p1: bool) -> i64 {
             .map(|(l, r)| l.abs_diff(r))
             .sum();
 
-        return result as i64;
-    } else {
+        result.into()
+    }
+
+    fn part2(&self, (l_nums, r_nums): &Self::Input) -> Answer {
         let mut r_nums_hash = HashMap::<u32, usize>::new();
-        for n in r_nums {
+        for &n in r_nums {
             let entry = r_nums_hash.entry(n);
             *entry.or_insert(0) += 1;
         }
 
         let result: usize = l_nums
-            .into_iter()
-            .map(|n| (n as usize) * r_nums_hash.get(&n).unwrap_or(&0))
+            .iter()
+            .map(|&n| (n as usize) * r_nums_hash.get(&n).unwrap_or(&0))
             .sum();
 
-        return result as i64;
+        result.into()
     }
 }
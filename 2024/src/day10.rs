@@ -1,52 +1,8 @@
-use itertools::Itertools;
-
-#[derive(Clone, Copy, Debug, Default, PartialOrd, Ord, PartialEq, Eq)]
-struct Pos {
-    x: u32,
-    y: u32,
-}
-
-impl Pos {
-    fn offset(&self, x: i32, y: i32) -> Pos {
-        Pos {
-            x: (self.x as i32 + x) as u32,
-            y: (self.y as i32 + y) as u32,
-        }
-    }
-
-    fn neighbors(&self, width: usize, height: usize) -> impl Iterator<Item = Self> {
-        let mut idx = 0;
-        let mut result = [Pos::default(); 4];
-
-        let mut pos = self.offset(0, 1);
-        if (pos.y as usize) < height {
-            result[idx] = pos;
-            idx += 1;
-        }
-        pos = self.offset(1, 0);
-        if (pos.x as usize) < width {
-            result[idx] = pos;
-            idx += 1;
-        }
-        if self.y > 0 {
-            result[idx] = self.offset(0, -1);
-            idx += 1;
-        }
-        if self.x > 0 {
-            result[idx] = self.offset(-1, 0);
-            idx += 1;
-        }
-        result.into_iter().take(idx)
-    }
-
-    fn to_idx(&self, width: usize) -> usize {
-        self.x as usize + (self.y as usize) * width
-    }
-}
+use aoc_common::{grid::Grid, vec2::Vec2, Answer, Solution};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Node {
-    pos: Pos,
+    pos: Vec2,
     elev: u8,
 }
 
@@ -56,7 +12,7 @@ struct Tree {
 }
 
 impl Tree {
-    fn new(head: Pos, elev: u8) -> Self {
+    fn new(head: Vec2, elev: u8) -> Self {
         Tree {
             paths: vec![vec![Node { pos: head, elev }]],
         }
@@ -64,7 +20,7 @@ impl Tree {
 
     const TOP_ELEV: u8 = 9;
 
-    fn find_trails(&mut self, map: &[u8], width: usize, height: usize) {
+    fn find_trails(&mut self, map: &Grid<u8>) {
         let Self { paths } = self;
 
         let mut new_paths = Vec::<Vec<Node>>::new();
@@ -75,9 +31,9 @@ impl Tree {
                 let node = path.last().unwrap().clone();
 
                 let mut is_new_path = false;
-                for pos in node.pos.neighbors(width, height) {
+                for pos in map.neighbors4(node.pos) {
                     let elev_should_be = node.elev + 1;
-                    let elev_is = map[pos.to_idx(width)];
+                    let elev_is = map[pos];
                     if elev_is != elev_should_be {
                         continue;
                     }
@@ -101,7 +57,7 @@ impl Tree {
             for i in to_remove.drain(..).rev() {
                 paths.swap_remove(i);
             }
-            paths.extend(new_paths.drain(..));
+            paths.append(&mut new_paths);
             paths.retain_mut(|p| {
                 let last = p.last().unwrap();
                 if last.elev == Self::TOP_ELEV {
@@ -131,43 +87,56 @@ fn test_p1() {
         9876\n\
     ";
 
-    assert_eq!(1, day10(s, true));
+    assert_eq!(Answer::Int(1), Day10.part1(&Day10.parse(s).unwrap()));
 }
 
-pub fn day10(data: &str, p1: bool) -> i64 {
-    let map = data
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_digit(10).expect("digit") as u8)
-                .collect_vec()
-        })
-        .collect_vec();
-
-    let width = map.first().expect("at least one line").len();
-    assert!(map.iter().all(|l| l.len() == width));
-    let height = map.len();
-    let map = map.into_iter().flatten().collect_vec();
-
-    let pos_iter = (0..height as u32).flat_map(|y| (0..width as u32).map(move |x| Pos { x, y }));
-    let result: usize = pos_iter
-        .map(|pos| {
-            if map[pos.to_idx(width)] != 0 {
-                return 0;
-            }
+pub struct TopoMap {
+    map: Grid<u8>,
+}
 
-            let mut tree = Tree::new(pos, 0);
-            tree.find_trails(&map, width, height);
+impl TopoMap {
+    /// Count the hiking trails of all trailheads, where with `distinct_ends` only the trails
+    /// ending at different positions are counted.
+    fn count_trails(&self, distinct_ends: bool) -> i64 {
+        let result: usize = self
+            .map
+            .iter()
+            .map(|(pos, &elev)| {
+                if elev != 0 {
+                    return 0;
+                }
 
-            if p1 {
-                tree.paths.dedup_by_key(|p| p.last().unwrap().pos);
-                tree.paths.len()
-            } else {
+                let mut tree = Tree::new(pos, 0);
+                tree.find_trails(&self.map);
+
+                if distinct_ends {
+                    tree.paths.dedup_by_key(|p| p.last().unwrap().pos);
+                }
                 tree.paths.len()
-            }
-        })
-        .sum();
+            })
+            .sum();
+
+        result as i64
+    }
+}
 
-    result as i64
+#[derive(Clone)]
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = TopoMap;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let (map, []) = Grid::parse(data, [], |c| c.to_digit(10).map(|d| d as u8))?;
+
+        Ok(TopoMap { map })
+    }
+
+    fn part1(&self, map: &Self::Input) -> Answer {
+        map.count_trails(true).into()
+    }
+
+    fn part2(&self, map: &Self::Input) -> Answer {
+        map.count_trails(false).into()
+    }
 }
@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
+use aoc_common::{parse, Answer, Solution};
 use itertools::Itertools;
 use rayon::iter::{ParallelBridge, ParallelIterator};
-use tqdm::Iter;
 
 fn count_digits(n: u64) -> u32 {
     if n == 0 {
@@ -15,20 +15,20 @@ fn count_digits(n: u64) -> u32 {
 /// Apply rules to stones list.
 ///
 /// - If the stone is engraved with the number 0, it is replaced by a stone engraved with the
-///     number 1.
+///   number 1.
 /// - If the stone is engraved with a number that has an even number of digits, it is replaced by
-///     two stones. The left half of the digits are engraved on the new left stone, and the right
-///     half of the digits are engraved on the new right stone. (The new numbers don't keep extra
-///     leading zeroes: 1000 would become stones 10 and 0.)
+///   two stones. The left half of the digits are engraved on the new left stone, and the right
+///   half of the digits are engraved on the new right stone. (The new numbers don't keep extra
+///   leading zeroes: 1000 would become stones 10 and 0.)
 /// - If none of the other rules apply, the stone is replaced by a new stone; the old stone's
-///     number multiplied by 2024 is engraved on the new stone.
+///   number multiplied by 2024 is engraved on the new stone.
 fn apply_rules_once(n: u64) -> (u64, Option<u64>) {
     if n == 0 {
         return (1, None);
     }
 
     let n_digits = count_digits(n);
-    if n_digits % 2 == 0 {
+    if n_digits.is_multiple_of(2) {
         let divider = 10_u64.pow(n_digits / 2);
         let lhs = n / divider;
         let rhs = n % divider;
@@ -53,7 +53,7 @@ fn apply_rules(mut nums: Vec<u64>, iters: u8) -> Vec<u64> {
     nums
 }
 
-#[repr(packed)]
+#[repr(C, packed)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Stone {
     num: u64,
@@ -112,42 +112,70 @@ impl Stone {
     }
 }
 
-pub fn day11(data: &str, p1: bool) -> i64 {
-    let nums = data
-        .trim()
-        .split(' ')
-        .map(|s| s.parse::<u64>().expect("unsigned number"))
-        .collect_vec();
+#[derive(Clone)]
+pub struct Day11 {
+    /// How often the stones blink in part 1.
+    pub p1_blinks: u8,
+    /// How often the stones blink in part 2.
+    pub p2_blinks: u8,
+}
+
+impl Day11 {
+    pub const DEFAULT: Self = Self {
+        p1_blinks: 25,
+        p2_blinks: 75,
+    };
+}
+
+impl Solution for Day11 {
+    type Input = Vec<u64>;
+
+    aoc_common::params!(p1_blinks, p2_blinks);
 
-    if p1 {
-        let nums = apply_rules(nums, 25);
-        return nums.len() as i64;
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        data.split_whitespace()
+            .map(|s| parse::from_str::<u64>(data, s))
+            .try_collect()
     }
 
-    let nums = apply_rules(nums, 35);
-    let result: usize = nums
-        .into_iter()
-        .tqdm()
-        .map(|num| Stone { num, iters: 35 })
-        .par_bridge()
-        .map_init(
-            || (Vec::new(), Vec::new(), HashMap::new()),
-            |vecs, stone: Stone| {
-                if let Some(&c) = vecs.2.get(&stone) {
-                    return c;
-                }
-                let count = stone.apply_rules_and_count(vecs, 75, 100, 1_000_000);
-                vecs.2.entry(stone).or_insert(count);
-                count
-            },
-        )
-        .sum();
-
-    result as i64
+    fn part1(&self, nums: &Self::Input) -> Answer {
+        apply_rules(nums.clone(), self.p1_blinks).len().into()
+    }
+
+    fn part2(&self, nums: &Self::Input) -> Answer {
+        // Expand the first blinks directly, so that there are enough stones to spread over all
+        // threads.
+        let expanded_blinks = self.p2_blinks.min(35);
+        let nums = apply_rules(nums.clone(), expanded_blinks);
+        let result: usize = nums
+            .into_iter()
+            .map(|num| Stone {
+                num,
+                iters: expanded_blinks,
+            })
+            .par_bridge()
+            .map_init(
+                || (Vec::new(), Vec::new(), HashMap::new()),
+                |vecs, stone: Stone| {
+                    if let Some(&c) = vecs.2.get(&stone) {
+                        return c;
+                    }
+                    let count = stone.apply_rules_and_count(vecs, self.p2_blinks, 100, 1_000_000);
+                    vecs.2.entry(stone).or_insert(count);
+                    count
+                },
+            )
+            .sum();
+
+        result.into()
+    }
 }
 
 #[test]
 fn test_p1() {
     assert_eq!(vec![1, 2], apply_rules(vec![12], 1));
-    assert_eq!(55312, day11("125 17", true));
+    assert_eq!(
+        Answer::Int(55312),
+        Day11::DEFAULT.part1(&Day11::DEFAULT.parse("125 17").unwrap())
+    );
 }
@@ -1,51 +1,17 @@
+use aoc_common::{dir::Dir4, grid::Grid, vec2::Vec2, Answer, Solution};
 use itertools::Itertools;
 
 #[derive(Clone, Debug)]
-struct Square {
+pub struct Square {
     id: char,
     visited: bool,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
-pub struct Vec2(pub i32, pub i32);
-impl Vec2 {
-    pub fn is_in_bounds(self, width: usize, height: usize) -> bool {
-        (self.x() as i64) < (width as i64)
-            && (self.y() as i64) < (height as i64)
-            && self.x() >= 0
-            && self.y() >= 0
-    }
-
-    pub fn to_idx(self, width: usize) -> usize {
-        (self.0 as usize).saturating_add((self.1 as usize).saturating_mul(width))
-    }
-
-    pub fn offset(&self, x: i32, y: i32) -> Vec2 {
-        Vec2(self.x() + x, self.y() + y)
-    }
-
-    pub fn offset_vec(mut self, offset: Vec2) -> Self {
-        self.0 += offset.0;
-        self.1 += offset.1;
-        self
-    }
-
-    pub fn x(&self) -> i32 {
-        self.0
-    }
-    pub fn y(&self) -> i32 {
-        self.1
-    }
-
-    pub fn neighbors(&self) -> impl Iterator<Item = (Self, Dir)> {
-        [
-            (self.offset(0, -1), Dir::Up),
-            (self.offset(1, 0), Dir::Right),
-            (self.offset(0, 1), Dir::Down),
-            (self.offset(-1, 0), Dir::Left),
-        ]
+/// The four orthogonal neighbors of `pos` with the direction they are in.
+fn neighbors(pos: Vec2) -> impl Iterator<Item = (Vec2, Dir4)> {
+    Dir4::ALL
         .into_iter()
-    }
+        .map(move |dir| (pos + dir.to_vec2(), dir))
 }
 
 #[derive(Debug)]
@@ -58,73 +24,23 @@ struct Plot {
     id: char,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum Dir {
-    Up = 0,
-    Right,
-    Down,
-    Left,
-}
-
-impl Dir {
-    /// Turn this direction 90° clockwise.
-    pub fn turn(self) -> Dir {
-        match self {
-            Self::Up => Self::Right,
-            Self::Right => Self::Down,
-            Self::Down => Self::Left,
-            Self::Left => Self::Up,
-        }
-    }
-
-    pub const ALL: [Dir; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
-    pub const N: usize = 4;
-
-    pub fn turn_n(self, times: i32) -> Dir {
-        let mut idx = ((self as i32) + times) % (Self::N as i32);
-        if idx < 0 {
-            idx = (Self::N as i32) + idx;
-        }
-        Self::ALL[idx as usize]
-    }
-
-    pub fn opposite(self) -> Dir {
-        match self {
-            Self::Up => Self::Down,
-            Self::Right => Self::Left,
-            Self::Down => Self::Up,
-            Self::Left => Self::Right,
-        }
-    }
-
-    pub fn into_vec2(self) -> Vec2 {
-        match self {
-            Self::Up => Vec2(0, -1),
-            Self::Right => Vec2(1, 0),
-            Self::Down => Vec2(0, 1),
-            Self::Left => Vec2(-1, 0),
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Side {
     pos: Vec2,
-    dir: Dir,
+    dir: Dir4,
 }
 
-fn find_plots(squares: &[Square], width: usize, height: usize) -> Vec<Plot> {
-    let mut squares = squares.iter().cloned().collect_vec();
+fn find_plots(squares: &Grid<Square>) -> Vec<Plot> {
+    let mut squares = squares.clone();
     let mut result = Vec::new();
 
     let mut plot_squares = Vec::new();
     let mut sides = Vec::new();
-    for y in 0..height {
-        for x in 0..width {
+    for y in 0..squares.height() {
+        for x in 0..squares.width() {
             let pos = Vec2(x as i32, y as i32);
-            let idx = pos.to_idx(width);
 
-            let sq = unsafe { squares.get_unchecked(idx).clone() };
+            let sq = squares[pos].clone();
             if sq.visited {
                 continue;
             }
@@ -141,27 +57,24 @@ fn find_plots(squares: &[Square], width: usize, height: usize) -> Vec<Plot> {
             // Find all `Square`s in a 4-neighbor connected component starting from `pos`.
             // During this flood-fill, also calculate area, perimiter and collect all
             // boundary edges.
-            plot_squares.push((pos, sq, idx));
-            while let Some((pos, sq, idx)) = plot_squares.pop() {
-                let sq_ref = unsafe { squares.get_unchecked_mut(idx) };
+            plot_squares.push((pos, sq));
+            while let Some((pos, sq)) = plot_squares.pop() {
+                let sq_ref = &mut squares[pos];
                 if sq_ref.visited {
                     continue;
                 }
                 sq_ref.visited = true;
                 plot.area += 1;
 
-                for (n_pos, n_dir) in pos.neighbors() {
-                    if !n_pos.is_in_bounds(width, height) {
+                for (n_pos, n_dir) in neighbors(pos) {
+                    let Some(n_sq) = squares.get(n_pos).cloned() else {
                         plot.perimeter += 1;
                         sides.push(Side {
                             pos: n_pos,
                             dir: n_dir,
                         });
                         continue;
-                    }
-
-                    let n_idx = n_pos.to_idx(width);
-                    let n_sq = unsafe { squares.get_unchecked(n_idx).clone() };
+                    };
 
                     let is_other = n_sq.id != sq.id;
                     plot.perimeter += is_other as u32;
@@ -173,15 +86,15 @@ fn find_plots(squares: &[Square], width: usize, height: usize) -> Vec<Plot> {
                     }
 
                     if !n_sq.visited && !is_other {
-                        plot_squares.push((n_pos, n_sq, n_idx));
+                        plot_squares.push((n_pos, n_sq));
                     }
                 }
             }
             sides.sort_unstable_by_key(|s| s.dir);
-            fn pos_dir_coord_predicate(dir: Dir) -> fn(&Side) -> i32 {
+            fn pos_dir_coord_predicate(dir: Dir4) -> fn(&Side) -> i32 {
                 match dir {
-                    Dir::Up | Dir::Down => |s: &Side| s.pos.y(),
-                    Dir::Left | Dir::Right => |s: &Side| s.pos.x(),
+                    Dir4::Up | Dir4::Down => |s: &Side| s.pos.y(),
+                    Dir4::Left | Dir4::Right => |s: &Side| s.pos.x(),
                 }
             }
             plot.sides = sides
@@ -194,7 +107,7 @@ fn find_plots(squares: &[Square], width: usize, height: usize) -> Vec<Plot> {
                     let mut group = group.collect_vec();
 
                     // Then, for each direction, group on the coordinate of that direction:
-                    // When dir = Dir::Up, the sides are on top, so we want all lines.
+                    // When dir = Dir4::Up, the sides are on top, so we want all lines.
                     // The resulting groups have all a common y coordinate.
                     let pred = pos_dir_coord_predicate(dir);
                     group.sort_unstable_by_key(pred);
@@ -209,11 +122,11 @@ fn find_plots(squares: &[Square], width: usize, height: usize) -> Vec<Plot> {
                         .map(move |mut chunk| {
                             // Finally, group consecutive coordinates opposite of the previous
                             // direction into one side.
-                            // E.g. when dir = Dir::Up, `chunk` contains all `Side`s with
+                            // E.g. when dir = Dir4::Up, `chunk` contains all `Side`s with
                             // the same y coordinate, group all consecutive x coordinates into
                             // one side, and count the resulting sides: `0, 1, 2, 4, 5, 10` =>
                             // `[0, 1, 2], [4, 5], [10]` => 3 sides.
-                            let pred = pos_dir_coord_predicate(dir.turn());
+                            let pred = pos_dir_coord_predicate(dir.rotate(1));
                             chunk.sort_unstable_by_key(pred);
                             chunk
                                 .into_iter()
@@ -233,34 +146,47 @@ fn find_plots(squares: &[Square], width: usize, height: usize) -> Vec<Plot> {
     result
 }
 
-pub fn day12(data: &str, _p1: bool) -> i64 {
-    let map = data
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            l.chars()
-                .map(|c| Square {
-                    id: c,
-                    visited: false,
-                })
-                .collect_vec()
-        })
-        .collect_vec();
+pub struct Garden {
+    squares: Grid<Square>,
+}
 
-    let width = map.first().expect("at least one line").len();
-    assert!(map.iter().all(|l| l.len() == width));
-    let height = map.len();
+impl Garden {
+    fn plots(&self) -> Vec<Plot> {
+        find_plots(&self.squares)
+    }
+}
 
-    let map = map.into_iter().flatten().collect_vec();
-    let plots = find_plots(&map, width, height);
+#[derive(Clone)]
+pub struct Day12;
 
-    let result: u64 = if _p1 {
-        plots.iter().map(|p| (p.perimeter * p.area) as u64).sum()
-    } else {
-        plots.iter().map(|p| (p.sides * p.area) as u64).sum()
-    };
+impl Solution for Day12 {
+    type Input = Garden;
 
-    // println!("{plots:#?}");
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let (squares, []) = Grid::parse(data, [], |c| {
+            Some(Square {
+                id: c,
+                visited: false,
+            })
+        })?;
+        Ok(Garden { squares })
+    }
+
+    fn part1(&self, garden: &Self::Input) -> Answer {
+        let result: u64 = garden
+            .plots()
+            .iter()
+            .map(|p| (p.perimeter * p.area) as u64)
+            .sum();
+        result.into()
+    }
 
-    result as i64
+    fn part2(&self, garden: &Self::Input) -> Answer {
+        let result: u64 = garden
+            .plots()
+            .iter()
+            .map(|p| (p.sides * p.area) as u64)
+            .sum();
+        result.into()
+    }
 }
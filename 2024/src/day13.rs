@@ -1,50 +1,17 @@
-use super::day12::Dir;
-use itertools::Itertools;
-use tqdm::Iter;
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
-pub struct Vec2(pub i64, pub i64);
-
-#[allow(dead_code)]
-impl Vec2 {
-    pub fn is_in_bounds(self, width: usize, height: usize) -> bool {
-        (self.x() as i64) < (width as i64)
-            && (self.y() as i64) < (height as i64)
-            && self.x() >= 0
-            && self.y() >= 0
-    }
-
-    pub fn to_idx(self, width: usize) -> usize {
-        (self.0 as usize) + (self.1 as usize) * width
-    }
-
-    pub fn offset(&self, x: i64, y: i64) -> Vec2 {
-        Vec2(self.x() + x, self.y() + y)
-    }
-
-    pub fn x(&self) -> i64 {
-        self.0
-    }
-    pub fn y(&self) -> i64 {
-        self.1
-    }
-
-    pub fn neighbors(&self) -> impl Iterator<Item = (Self, Dir)> {
-        [
-            (self.offset(0, -1), Dir::Up),
-            (self.offset(1, 0), Dir::Right),
-            (self.offset(0, 1), Dir::Down),
-            (self.offset(-1, 0), Dir::Left),
-        ]
-        .into_iter()
-    }
-}
-
-#[derive(Debug)]
-struct ClawCfg {
-    button_a: Vec2,
-    button_b: Vec2,
-    prize_pos: Vec2,
+use aoc_common::{parse, vec2::Vec2, Answer, Solution};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, line_ending, space1},
+    combinator::map,
+    error::context,
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
+};
+
+#[derive(Debug, Clone)]
+pub struct ClawCfg {
+    button_a: Vec2<i64>,
+    button_b: Vec2<i64>,
+    prize_pos: Vec2<i64>,
 }
 
 impl ClawCfg {
@@ -84,65 +51,54 @@ impl ClawCfg {
     }
 }
 
-pub fn day13(data: &str, p1: bool) -> i64 {
-    let lines = data.lines().map(str::trim).collect_vec();
-    let mut cfgs = lines
-        .split(|l| l.is_empty())
-        .map(|lines| {
-            let (btn_a_line, btn_b_line, prize_line) =
-                lines.into_iter().collect_tuple().expect("3 text lines");
-
-            fn parse_button(l: &str, btn: &str) -> Vec2 {
-                let (_btn, c, x, y) = l.split(' ').collect_tuple().expect("button format");
-                assert_eq!(c, btn);
-                let x = x.strip_suffix(',').expect("comma after `X+<num>`");
-
-                let (_, x) = x.trim().split_once("+").expect("X+<number> format");
-                let (_, y) = y.trim().split_once("+").expect("X+<number> format");
-
-                let x = x.parse::<i64>().expect("x is number");
-                let y = y.parse::<i64>().expect("y is number");
-                Vec2(x, y)
-            }
-
-            let button_a = parse_button(btn_a_line, "A:");
-            let button_b = parse_button(btn_b_line, "B:");
-
-            let (prize, prize_x, prize_y) =
-                prize_line.split(' ').collect_tuple().expect("prize line");
-            assert_eq!(prize, "Prize:");
-            let prize_x = prize_x
-                .strip_suffix(',')
-                .unwrap()
-                .strip_prefix("X=")
-                .unwrap();
-            let prize_y = prize_y.strip_prefix("Y=").unwrap();
-
-            let prize_x = prize_x.parse::<i64>().expect("prize x must be number");
-            let prize_y = prize_y.parse::<i64>().expect("prize y must be number");
-            let prize_pos = Vec2(prize_x, prize_y);
+#[derive(Clone)]
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<ClawCfg>;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        /// `<label> X<sep><x>, Y<sep><y>`.
+        fn xy<'a>(
+            label: &'a str,
+            sep: char,
+        ) -> impl FnMut(&'a str) -> parse::IResult<'a, Vec2<i64>> {
+            let x = preceded(pair(char('X'), char(sep)), parse::int);
+            let y = preceded(pair(char('Y'), char(sep)), parse::int);
+            map(
+                preceded(pair(tag(label), space1), separated_pair(x, tag(", "), y)),
+                Vec2::from,
+            )
+        }
 
-            ClawCfg {
+        let claw = map(
+            tuple((
+                context("button A", terminated(xy("Button A:", '+'), line_ending)),
+                context("button B", terminated(xy("Button B:", '+'), line_ending)),
+                context("prize", xy("Prize:", '=')),
+            )),
+            |(button_a, button_b, prize_pos)| ClawCfg {
                 button_a,
                 button_b,
                 prize_pos,
-            }
-        })
-        .collect_vec();
+            },
+        );
+        parse::all(data, parse::sections(claw))
+    }
+
+    fn part1(&self, cfgs: &Self::Input) -> Answer {
+        cfgs.iter()
+            .filter_map(|cfg| cfg.find_min_cost(3, 1))
+            .map(|(_, _, c)| c)
+            .sum::<i64>()
+            .into()
+    }
 
-    if !p1 {
+    fn part2(&self, cfgs: &Self::Input) -> Answer {
+        let mut cfgs = cfgs.clone();
         for cfg in cfgs.iter_mut() {
-            cfg.prize_pos = cfg.prize_pos.offset(10000000000000, 10000000000000);
+            cfg.prize_pos += Vec2(10000000000000, 10000000000000);
         }
         unimplemented!("problem 2");
     }
-
-    let result: i64 = cfgs
-        .iter()
-        .tqdm()
-        .filter_map(|cfg| cfg.find_min_cost(3, 1))
-        .map(|(_, _, c)| c)
-        .sum();
-
-    result as i64
 }
@@ -1,68 +1,40 @@
-use std::char;
-use std::num::ParseIntError;
-
-use crossterm::event::KeyCode;
+use aoc_common::{parse, vec2::Vec2, Answer, Solution};
 use itertools::Itertools;
-use nom::bytes::complete::tag;
-use nom::error::FromExternalError;
-use nom::{IResult, Parser};
-
-use super::day12::Vec2;
+use nom::{
+    character::complete::char,
+    combinator::{consumed, map},
+    error::context,
+    sequence::separated_pair,
+};
 
 #[derive(Debug, Clone)]
-struct Robot {
+pub struct Robot {
     pos: Vec2,
     velocity: Vec2,
 }
 
-pub fn parse_i32<
-    'a,
-    E: nom::error::ParseError<&'a str> + FromExternalError<&'a str, ParseIntError>,
->(
-    input: &'a str,
-) -> IResult<&'a str, i32, E> {
-    use nom::bytes::complete::tag;
-    use nom::character::complete::digit1;
-    use nom::combinator::{map_res, opt, recognize};
-    use nom::sequence::preceded;
-
-    let (i, number) = map_res(recognize(preceded(opt(tag("-")), digit1)), |s: &str| {
-        s.parse::<i32>()
-    })(input)?;
-
-    Ok((i, number))
-}
-
 impl Robot {
-    fn parse(line: &str) -> anyhow::Result<Robot> {
-        fn parse_vec(key: &str) -> impl FnMut(&str) -> IResult<&str, Vec2> + use<'_> {
-            move |input: &str| {
-                let (input, (x, y)) = nom::sequence::preceded(
-                    nom::sequence::tuple((tag(key), tag("="))),
-                    nom::sequence::separated_pair(parse_i32, tag(","), parse_i32),
-                )(input)?;
-
-                Ok((input, Vec2(x, y)))
-            }
-        }
-
-        let (_, (pos, velocity)) = nom::sequence::terminated(
-            nom::sequence::separated_pair(parse_vec("p"), tag(" "), parse_vec("v")),
-            nom::combinator::eof,
-        )
-        .parse(line)
-        .map_err(|e| e.to_owned())?;
-
-        Ok(Robot { pos, velocity })
+    /// A robot `p=<x>,<y> v=<x>,<y>`.
+    fn parse(input: &str) -> parse::IResult<'_, Robot> {
+        let robot = separated_pair(parse::vec2("p"), char(' '), parse::vec2("v"));
+        let mut robot = context(
+            "robot",
+            map(robot, |(pos, velocity)| Robot {
+                pos: Vec2::from(pos),
+                velocity: Vec2::from(velocity),
+            }),
+        );
+        robot(input)
     }
 
     fn tick(&mut self, time: i32, width: u32, height: u32) {
-        let (v_x, v_y) = if time < 0 {
-            (-self.velocity.x(), -self.velocity.y())
+        let velocity = if time < 0 {
+            -self.velocity
         } else {
-            (self.velocity.x(), self.velocity.y())
+            self.velocity
         };
-        let time = time.abs() as u32;
+        let (v_x, v_y) = (velocity.x(), velocity.y());
+        let time = time.unsigned_abs();
 
         let w_1 = width - 1;
         let h_1 = height - 1;
@@ -90,8 +62,7 @@ impl Robot {
             y = h_1 - y;
         }
 
-        self.pos.0 = x as i32;
-        self.pos.1 = y as i32;
+        self.pos = Vec2(x as i32, y as i32);
     }
 }
 
@@ -118,12 +89,12 @@ impl RobotsMap {
 
 impl std::fmt::Display for RobotsMap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let x_skip = if self.width % 2 == 0 {
+        let x_skip = if self.width.is_multiple_of(2) {
             u32::MAX
         } else {
             self.width / 2
         };
-        let y_skip = if self.height % 2 == 0 {
+        let y_skip = if self.height.is_multiple_of(2) {
             u32::MAX
         } else {
             self.height / 2
@@ -138,7 +109,7 @@ impl std::fmt::Display for RobotsMap {
                 .map(|x| {
                     let v = self.map[(x + y * self.width) as usize];
                     if v != 0 {
-                        char::from_digit(v as u32, 10).unwrap_or('?')
+                        char::from_digit(v, 10).unwrap_or('?')
                     } else {
                         '.'
                     }
@@ -150,13 +121,51 @@ impl std::fmt::Display for RobotsMap {
     }
 }
 
-pub fn day14(data: &str, p1: bool) -> i64 {
-    let mut robots: Vec<Robot> = data.lines().map(|l| Robot::parse(l)).try_collect().unwrap();
+#[derive(Clone)]
+pub struct Day14 {
+    /// The width of the space the robots move in.
+    pub width: u32,
+    /// The height of the space the robots move in.
+    pub height: u32,
+}
 
-    let width = 101;
-    let height = 103;
+impl Day14 {
+    pub const DEFAULT: Self = Self {
+        width: 101,
+        height: 103,
+    };
+}
+
+impl Solution for Day14 {
+    type Input = Vec<Robot>;
+
+    aoc_common::params!(width, height);
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let (width, height) = (self.width, self.height);
+        anyhow::ensure!(
+            width > 0 && height > 0,
+            "the space of {width}x{height} tiles is empty"
+        );
+
+        let robots = parse::all(data, parse::lines(consumed(Robot::parse)))?;
+        robots
+            .into_iter()
+            .map(|(at, robot)| {
+                let (x, y) = (robot.pos.x(), robot.pos.y());
+                if x < 0 || y < 0 || x as u32 >= width || y as u32 >= height {
+                    let msg = format!("outside of the space of {width}x{height} tiles");
+                    return Err(parse::error(data, at, msg));
+                }
+                Ok(robot)
+            })
+            .collect()
+    }
+
+    fn part1(&self, robots: &Self::Input) -> Answer {
+        let mut robots = robots.clone();
+        let (width, height) = (self.width, self.height);
 
-    if p1 {
         for r in robots.iter_mut() {
             r.tick(100, width, height);
         }
@@ -164,81 +173,51 @@ pub fn day14(data: &str, p1: bool) -> i64 {
             robots.iter().filter(|r| f(&r.pos)).count()
         }
 
-        println!("{}", RobotsMap::new(&robots, width, height));
+        log::debug!("{}", RobotsMap::new(&robots, width, height));
 
         let x_l = width / 2;
-        let x_r = if width % 2 == 0 { x_l } else { x_l + 1 };
+        let x_r = if width.is_multiple_of(2) {
+            x_l
+        } else {
+            x_l + 1
+        };
         let y_l = height / 2;
-        let y_r = if height % 2 == 0 { y_l } else { y_l + 1 };
+        let y_r = if height.is_multiple_of(2) {
+            y_l
+        } else {
+            y_l + 1
+        };
 
         let top_left = count_where(&robots, |p| p.x() < x_l as i32 && p.y() < y_l as i32);
         let top_right = count_where(&robots, |p| p.x() >= x_r as i32 && p.y() < y_l as i32);
         let bottom_left = count_where(&robots, |p| p.x() < x_l as i32 && p.y() >= y_r as i32);
         let bottom_right = count_where(&robots, |p| p.x() >= x_r as i32 && p.y() >= y_r as i32);
 
-        (dbg!(top_left) * dbg!(top_right) * dbg!(bottom_left) * dbg!(bottom_right)) as i64
-    } else {
-        let mut t = 7285_usize;
-        for r in robots.iter_mut() {
-            r.tick(t as i32, width, height);
-        }
-        loop {
-            t += 1;
-            for r in robots.iter_mut() {
-                r.tick(1, width, height);
-            }
+        log::info!(
+            "quadrants: top_left = {top_left}, top_right = {top_right}, \
+             bottom_left = {bottom_left}, bottom_right = {bottom_right}"
+        );
+        (top_left * top_right * bottom_left * bottom_right).into()
+    }
 
-            let ms1 = RobotsMap::new(&robots, width, height).to_string();
-            let mut robots2 = robots.clone();
+    /// The first tick at which no two robots are at the same position, which is when they
+    /// arrange into the picture of a christmas tree.
+    fn part2(&self, robots: &Self::Input) -> Answer {
+        let mut robots = robots.clone();
+        let (width, height) = (self.width, self.height);
 
-            for r in robots2.iter_mut() {
-                r.tick(1, width, height);
-            }
-            let ms2 = RobotsMap::new(&robots2, width, height).to_string();
-            for r in robots2.iter_mut() {
+        // The positions repeat after `width * height` ticks at the latest.
+        for t in 1..=width * height {
+            for r in robots.iter_mut() {
                 r.tick(1, width, height);
             }
 
-            let ms3 = RobotsMap::new(&robots2, width, height).to_string();
-
-            println!(
-                "{}",
-                ms1.lines()
-                    .zip(ms2.lines())
-                    .zip(ms3.lines())
-                    .map(|((l_1, l_2), l_3)| { [l_1, l_2, l_3].join("    ") })
-                    .join("\n")
-            );
-            println!("time = {}", t);
-
-            crossterm::terminal::enable_raw_mode().unwrap();
-            while let Ok(evt) = crossterm::event::read() {
-                match evt {
-                    crossterm::event::Event::Key(crossterm::event::KeyEvent {
-                        code: KeyCode::Enter,
-                        ..
-                    }) => break,
-                    crossterm::event::Event::Key(crossterm::event::KeyEvent {
-                        code: KeyCode::Backspace,
-                        ..
-                    }) => {
-                        for r in robots.iter_mut() {
-                            r.tick(-2, width, height);
-                        }
-                        t -= 2;
-                        break;
-                    }
-                    crossterm::event::Event::Key(crossterm::event::KeyEvent {
-                        code: KeyCode::Esc,
-                        ..
-                    }) => {
-                        crossterm::terminal::disable_raw_mode().unwrap();
-                        return t as i64;
-                    }
-                    e => println!("{e:?}"),
-                }
+            let map = RobotsMap::new(&robots, width, height);
+            if map.map.iter().all(|&n| n <= 1) {
+                log::debug!("time = {t}\n{map}");
+                return t.into();
             }
-            crossterm::terminal::disable_raw_mode().unwrap();
         }
+        panic!("the robots overlap at every tick");
     }
 }
@@ -1,9 +1,9 @@
 use std::collections::HashSet;
 
+use aoc_common::{dir::Dir4, grid::Grid, vec2::Vec2, Answer, Solution};
 use itertools::Itertools;
 
 use crate::{
-    day12::{Dir, Vec2},
     day18::{self, shortest_path::print_map},
     day21::dijkstra,
 };
@@ -27,128 +27,121 @@ impl std::fmt::Display for StartEnd {
 }
 type Loc = day18::Loc<StartEnd>;
 
-pub fn day16(data: &str, p1: bool) -> i64 {
-    let mut start = Vec2(0, 0);
-    let mut end = Vec2(0, 0);
-
-    let map = data
-        .lines()
-        .filter(|l| !l.is_empty())
-        .enumerate()
-        .map(|(y, l)| {
-            l.chars()
-                .enumerate()
-                .inspect(|(x, c)| {
-                    if *c == 'S' {
-                        start = Vec2(*x as i32, y as i32);
-                    } else if *c == 'E' {
-                        end = Vec2(*x as i32, y as i32);
-                    }
-                })
-                .map(|(_, c)| c)
-                .collect_vec()
-        })
-        .collect_vec();
-
-    let height = map.len();
-    let width = map.first().unwrap().len();
-    assert!(map.iter().all(|l| l.len() == width));
-
-    let map = map
-        .into_iter()
-        .flatten()
-        .map(|c| match c {
-            '#' => Loc::Obstacle,
-            '.' | 'S' | 'E' => Loc::Empty,
-            _ => unreachable!("invalid char {c}"),
-        })
-        .collect_vec();
-
-    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-    struct MapNode(Vec2, Dir);
-
-    #[derive(Debug)]
-    struct Context<'m> {
-        width: usize,
-        height: usize,
-        map: &'m [Loc],
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MapNode(Vec2, Dir4);
+
+#[derive(Debug)]
+struct Context<'m> {
+    map: &'m Grid<Loc>,
+}
+
+impl<'a> dijkstra::Node<Context<'a>> for MapNode {
+    fn neighbors(&self, ctx: &Context<'a>) -> impl Iterator<Item = (Self, i32)> {
+        let n0 = {
+            let next_pos = self.0 + self.1.to_vec2();
+            if ctx.map.get(next_pos) == Some(&Loc::Empty) {
+                Some((MapNode(next_pos, self.1), 1))
+            } else {
+                None
+            }
+        };
+        n0.into_iter().chain([
+            (MapNode(self.0, self.1.rotate(1)), 1000),
+            (MapNode(self.0, self.1.rotate(-1)), 1000),
+        ])
+    }
+}
+
+pub struct Maze {
+    map: Grid<Loc>,
+    start: Vec2,
+    end: Vec2,
+}
+
+impl Maze {
+    /// Find the shortest paths from the start facing east to the end.
+    ///
+    /// Returns the solver and its end nodes.
+    fn shortest_paths(&self) -> (dijkstra::ShortestPath<MapNode>, Box<[MapNode]>) {
+        let mut sp = dijkstra::ShortestPath::new(MapNode(self.start, Dir4::Right));
+
+        let end_nodes = sp.calc(&Context { map: &self.map }, |n: &MapNode, _| {
+            n.0 == self.end
+        });
+        (sp, end_nodes)
     }
 
-    impl<'a> dijkstra::Node<Context<'a>> for MapNode {
-        fn neighbors(&self, ctx: &Context<'a>) -> impl Iterator<Item = (Self, i32)> {
-            let n0 = {
-                let next_pos = self.0.offset_vec(self.1.into_vec2());
-                if next_pos.is_in_bounds(ctx.width, ctx.height)
-                    && ctx.map[next_pos.to_idx(ctx.width)] == Loc::Empty
-                {
-                    Some((MapNode(next_pos, self.1), 1))
-                } else {
-                    None
-                }
-            };
-            n0.into_iter().chain([
-                (MapNode(self.0, self.1.turn_n(1)), 1000),
-                (MapNode(self.0, self.1.turn_n(-1)), 1000),
-            ])
+    /// Print the map with all nodes of `paths` and start and end markers.
+    fn print_paths<'a>(&self, paths: impl IntoIterator<Item = &'a MapNode>) {
+        if !log::log_enabled!(log::Level::Debug) {
+            return;
+        }
+
+        let mut path_map = self.map.clone();
+        for n in paths {
+            path_map[n.0] = Loc::Path;
         }
+        path_map[self.start] = Loc::Custom(StartEnd::Start);
+        path_map[self.end] = Loc::Custom(StartEnd::End);
+
+        print_map(&path_map);
     }
+}
 
-    let mut sp = dijkstra::ShortestPath::new(MapNode(start, Dir::Right));
+#[derive(Clone)]
+pub struct Day16;
 
-    let end_nodes = sp.calc(
-        &Context {
-            width,
-            height,
-            map: &map,
-        },
-        |n: &MapNode, _| n.0 == end,
-    );
+impl Solution for Day16 {
+    type Input = Maze;
 
-    if p1 {
-        let (path, dist) = sp.paths(&end_nodes).next().unwrap();
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let (map, [start, end]) = Grid::parse(data, ['S', 'E'], |c| match c {
+            '#' => Some(Loc::Obstacle),
+            '.' | 'S' | 'E' => Some(Loc::Empty),
+            _ => None,
+        })?;
 
-        let mut path_map = map.clone();
-        for n in path {
-            path_map[n.0.to_idx(width)] = Loc::Path;
-        }
-        path_map[start.to_idx(width)] = Loc::Custom(StartEnd::Start);
-        path_map[end.to_idx(width)] = Loc::Custom(StartEnd::End);
+        Ok(Maze { map, start, end })
+    }
 
-        print_map(&path_map, width, height);
+    fn part1(&self, maze: &Self::Input) -> Answer {
+        let (sp, end_nodes) = maze.shortest_paths();
+        let (path, dist) = sp.paths(&end_nodes).next().unwrap();
+
+        maze.print_paths(path);
 
-        return dist as i64;
+        dist.into()
     }
 
-    let mut last_dist = None;
-    let paths = sp
-        .paths(&end_nodes)
-        .sorted_by_key(|(_, dist)| *dist)
-        .take_while(|(_, dist)| {
-            if let Some(last_dist) = last_dist {
-                if last_dist < *dist {
-                    return false;
+    fn part2(&self, maze: &Self::Input) -> Answer {
+        let (sp, end_nodes) = maze.shortest_paths();
+
+        let mut last_dist = None;
+        let paths = sp
+            .paths(&end_nodes)
+            .sorted_by_key(|(_, dist)| *dist)
+            .take_while(|(_, dist)| {
+                if let Some(last_dist) = last_dist {
+                    if last_dist < *dist {
+                        return false;
+                    }
                 }
+                last_dist = Some(*dist);
+                true
+            })
+            .collect_vec();
+
+        log::info!("{} paths", paths.len());
+
+        let mut cells = HashSet::new();
+        for (path, dist) in &paths {
+            log::debug!("dist: {dist}");
+            for n in path {
+                cells.insert(n.0);
             }
-            last_dist = Some(*dist);
-            true
-        })
-        .collect_vec();
-
-    println!("{} paths", paths.len());
-
-    let mut path_map = map.clone();
-    let mut cells = HashSet::new();
-    for (path, dist) in paths {
-        println!("dist: {dist}");
-        for n in path {
-            cells.insert(n.0);
-            path_map[n.0.to_idx(width)] = Loc::Path;
         }
-    }
-    path_map[start.to_idx(width)] = Loc::Custom(StartEnd::Start);
-    path_map[end.to_idx(width)] = Loc::Custom(StartEnd::End);
-
-    print_map(&path_map, width, height);
+        maze.print_paths(paths.iter().flat_map(|(path, _)| path.iter().copied()));
 
-    cells.len() as i64
+        cells.len().into()
+    }
 }
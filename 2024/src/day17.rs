@@ -1,16 +1,23 @@
-use anyhow::Context;
+use aoc_common::{parse, Answer, Solution};
 use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::line_ending,
+    combinator::{map, verify},
+    error::context,
+    sequence::{preceded, separated_pair, terminated, tuple},
+};
 
 #[derive(Clone, Copy, Debug)]
 #[repr(usize)]
-enum Register {
+pub enum Register {
     A = 0,
     B,
     C,
 }
 
 #[derive(Clone, Copy, Debug)]
-enum Operand {
+pub enum Operand {
     Literal(u8),
     Register(Register),
     Reserved,
@@ -60,7 +67,7 @@ impl Operand {
 #[derive(Clone, Copy, Debug)]
 #[repr(u8)]
 #[allow(dead_code)]
-enum Op {
+pub enum Op {
     Adv = 0,
     Bxl = 1,
     Bst = 2,
@@ -140,56 +147,68 @@ struct System {
     pub out: Vec<u8>,
 }
 
-pub fn day17(data: &str, p1: bool) -> i64 {
-    let lines = data.trim().lines().collect_vec();
-    let (regs, program) = lines
-        .split(|l| l.is_empty())
-        .collect_tuple()
-        .expect("registers then program");
-    let (reg_a, reg_b, reg_c) = regs
-        .into_iter()
-        .map(|r| {
-            let (_, val) = r.split_once(':').expect("register then `:` then val");
-            val.trim()
-                .parse::<i64>()
-                .with_context(|| format!("invalid '{val}'"))
-                .unwrap()
-        })
-        .collect_tuple()
-        .expect("three register values");
-    let program = program.join("");
-    let (_, program) = program.split_once(':').expect("program then `:` then vals");
-    let program_nums = program
-        .trim()
-        .split(',')
-        .map(|v| v.parse::<u8>().unwrap())
-        .collect_vec();
-    let program = program_nums
-        .iter()
-        .tuples()
-        .map(|(&op, &operand)| {
-            let op = Op::from(op);
-            let operand = Operand::new(op, operand);
-            (op, operand)
+pub struct Program {
+    regs: [i64; 3],
+    program: Vec<(Op, Operand)>,
+}
+
+#[derive(Clone)]
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Program;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let register = |name| {
+            let register = preceded(tag("Register "), parse::key_value(tag(name), parse::int));
+            context("register", map(register, |(_, value)| value))
+        };
+        let registers = tuple((
+            terminated(register("A"), line_ending),
+            terminated(register("B"), line_ending),
+            register("C"),
+        ));
+        let three_bit = context("3-bit number", verify(parse::uint::<u8>, |&n| n <= 7));
+        let program = parse::key_value(tag("Program"), parse::comma_list(three_bit));
+
+        let ((reg_a, reg_b, reg_c), (_, program_nums)) = parse::all(
+            data,
+            separated_pair(registers, parse::section_break, program),
+        )?;
+        let program = program_nums
+            .iter()
+            .tuples()
+            .map(|(&op, &operand)| {
+                let op = Op::from(op);
+                let operand = Operand::new(op, operand);
+                (op, operand)
+            })
+            .collect_vec();
+
+        log::debug!("program: {program:?}");
+
+        Ok(Program {
+            regs: [reg_a, reg_b, reg_c],
+            program,
         })
-        .collect_vec();
-
-    println!("program: {program:?}");
-    let mut sys = System {
-        regs: [reg_a, reg_b, reg_c],
-        pc: 0,
-        out: Vec::new(),
-    };
-    println!("{sys:?}");
-
-    if p1 {
-        while let Some((op, operand)) = program.get(sys.pc).copied() {
+    }
+
+    fn part1(&self, program: &Self::Input) -> Answer {
+        let mut sys = System {
+            regs: program.regs,
+            pc: 0,
+            out: Vec::new(),
+        };
+        log::debug!("{sys:?}");
+
+        while let Some((op, operand)) = program.program.get(sys.pc).copied() {
             op.exec(&mut sys, operand);
         }
 
-        println!("output: {}", sys.out.iter().join(","));
-    } else {
+        sys.out.iter().join(",").into()
+    }
+
+    fn part2(&self, _program: &Self::Input) -> Answer {
         todo!()
     }
-    0
 }
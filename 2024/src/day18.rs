@@ -1,7 +1,6 @@
+use aoc_common::{grid::Grid, parse, vec2::Vec2, Answer, Solution};
 use itertools::Itertools;
 
-use crate::day12::Vec2;
-
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[repr(u8)]
 pub enum Loc<T> {
@@ -11,6 +10,17 @@ pub enum Loc<T> {
     Custom(T),
 }
 
+impl<T: std::fmt::Display> std::fmt::Display for Loc<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, " "),
+            Self::Obstacle => write!(f, "#"),
+            Self::Path => write!(f, "."),
+            Self::Custom(v) => write!(f, "{v}"),
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 struct DeadEnd;
 impl std::fmt::Display for DeadEnd {
@@ -20,24 +30,33 @@ impl std::fmt::Display for DeadEnd {
 }
 
 pub mod shortest_path {
+    use aoc_common::grid::Grid;
+    use itertools::Itertools;
+
     pub use super::Loc;
-    pub use crate::day12::{Dir, Vec2};
+    use aoc_common::dir::Dir4;
+    pub use aoc_common::vec2::Vec2;
 
     #[derive(Debug, Clone)]
-    pub struct Node([(Dir, u32); 4]);
+    pub struct Node([(Dir4, u32); 4]);
+    impl Default for Node {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
     impl Node {
         pub fn new() -> Self {
             Node([
-                (Dir::Up, u32::MAX),
-                (Dir::Right, u32::MAX),
-                (Dir::Down, u32::MAX),
-                (Dir::Left, u32::MAX),
+                (Dir4::Up, u32::MAX),
+                (Dir4::Right, u32::MAX),
+                (Dir4::Down, u32::MAX),
+                (Dir4::Left, u32::MAX),
             ])
         }
 
         /// Returns `true` if the path from `went_to` was shorter than previously best path.
         /// Otherwise returns `false`.
-        pub fn update_with(&mut self, went_to: Dir, steps: u32) -> bool {
+        pub fn update_with(&mut self, went_to: Dir4, steps: u32) -> bool {
             let val = &mut self.0[went_to.opposite() as usize];
             if val.1 > steps {
                 val.1 = steps;
@@ -50,38 +69,27 @@ pub mod shortest_path {
 
     #[derive(Debug, Clone)]
     pub struct ShortestPath {
-        map: Vec<Node>,
-        width: usize,
-        height: usize,
+        map: Grid<Node>,
         start: Vec2,
     }
 
     impl ShortestPath {
         pub fn new(start: Vec2, width: usize, height: usize) -> Self {
             Self {
-                map: Vec::new(),
-                width,
-                height,
+                map: Grid::new(width, height, Node::new()),
                 start,
             }
         }
 
-        /// Find all shortest paths between [`Self::start`] and all empty tiles in the map.
-        pub fn calc<T>(&mut self, obst_map: &[Loc<T>])
+        /// Find all shortest paths from the start to all empty tiles in the map.
+        pub fn calc<T>(&mut self, obst_map: &Grid<Loc<T>>)
         where
             T: Clone + Eq,
         {
-            let Self {
-                map,
-                width,
-                height,
-                start,
-            } = self;
-            let width = *width;
-            let height = *height;
+            let Self { map, start } = self;
             let start = *start;
 
-            *map = vec![Node::new(); width * height];
+            map.cells_mut().fill(Node::new());
             let mut paths = vec![(start, 0_u32)];
             let mut new_paths = Vec::<(Vec2, u32)>::new();
 
@@ -89,12 +97,13 @@ pub mod shortest_path {
                 paths.retain_mut(|(pos, steps)| {
                     *steps += 1;
                     let mut found_one = false;
-                    for (next_pos, dir) in pos
-                        .neighbors()
-                        .filter(|(pos, _)| pos.is_in_bounds(width, height))
-                        .filter(|(pos, _)| obst_map[pos.to_idx(width)] != Loc::Obstacle)
+                    let from = *pos;
+                    for (next_pos, dir) in Dir4::ALL
+                        .into_iter()
+                        .map(|dir| (from + dir.to_vec2(), dir))
+                        .filter(|(pos, _)| obst_map.get(*pos).is_some_and(|l| *l != Loc::Obstacle))
                     {
-                        if map[next_pos.to_idx(width)].update_with(dir, *steps) {
+                        if map[next_pos].update_with(dir, *steps) {
                             if found_one {
                                 new_paths.push((next_pos, *steps));
                             } else {
@@ -105,14 +114,14 @@ pub mod shortest_path {
                     }
                     found_one
                 });
-                paths.extend(new_paths.drain(..));
+                paths.append(&mut new_paths);
             }
         }
 
         /// Get the amount of steps needed to `end`, return [`None`] if there is no path to `end`.
         pub fn steps_to(&self, end: Vec2) -> Option<u32> {
             self.map
-                .get(end.to_idx(self.width))?
+                .get(end)?
                 .0
                 .iter()
                 .map(|(_, s)| *s)
@@ -120,109 +129,144 @@ pub mod shortest_path {
                 .min()
         }
 
-        /// Get the path from [`Self::start`] to `end` as a series (point, number of steps at that point) pairs.
+        /// Get the path from the start to `end` as a series (point, number of steps at that point) pairs.
         pub fn get_path(&self, end: Vec2) -> Vec<(Vec2, u32)> {
             let mut pos = end;
             let start = self.start;
-            let width = self.width;
-            let height = self.height;
             let mut positions = Vec::new();
-            while (pos.x() != start.x() || pos.y() != start.y()) && pos.is_in_bounds(width, height)
-            {
-                let idx = pos.to_idx(width);
-                let (dir, steps) = *self.map[idx].0.iter().min_by_key(|(_, s)| s).unwrap();
+            while pos != start && self.map.contains(pos) {
+                let (dir, steps) = *self.map[pos].0.iter().min_by_key(|(_, s)| s).unwrap();
                 if steps == u32::MAX {
                     return positions;
                 }
                 positions.push((pos, steps));
-                pos = pos.offset_vec(dir.into_vec2());
+                pos += dir.to_vec2();
             }
             positions.push((self.start, 0));
             positions.reverse();
             positions
         }
 
-        /// Fill the map with the found path from [`Self::start`] to `end`.
-        pub fn fill_path<T>(&self, path_map: &mut Vec<Loc<T>>, end: Vec2) {
+        /// Fill the map with the found path from the start to `end`.
+        pub fn fill_path<T>(&self, path_map: &mut Grid<Loc<T>>, end: Vec2) {
             let mut pos = end;
             let start = self.start;
-            let width = self.width;
-            let height = self.height;
-            while (pos.x() != start.x() || pos.y() != start.y()) && pos.is_in_bounds(width, height)
-            {
-                let idx = pos.to_idx(width);
-                let (dir, steps) = *self.map[idx].0.iter().min_by_key(|(_, s)| s).unwrap();
-                path_map[idx] = Loc::Path;
+            while pos != start && self.map.contains(pos) {
+                let (dir, steps) = *self.map[pos].0.iter().min_by_key(|(_, s)| s).unwrap();
+                path_map[pos] = Loc::Path;
                 if steps == u32::MAX {
                     return;
                 }
-                pos = pos.offset_vec(dir.into_vec2());
+                pos += dir.to_vec2();
             }
-            path_map[start.to_idx(width)] = Loc::Path;
+            path_map[start] = Loc::Path;
         }
     }
 
-    pub fn print_map<T>(m: &Vec<Loc<T>>, width: usize, height: usize)
+    pub fn print_map<T>(m: &Grid<Loc<T>>)
     where
         T: std::fmt::Display,
     {
-        for y in 0..height as usize {
-            for x in 0..width as usize {
-                let c = match &m[x + y * width] {
-                    Loc::Obstacle => '#',
-                    Loc::Path => '.',
-                    Loc::Custom(v) => {
-                        print!("{v}");
-                        continue;
-                    }
-                    _ => ' ',
-                };
-                print!("{c}");
-            }
-            println!();
+        if !log::log_enabled!(log::Level::Debug) {
+            return;
+        }
+
+        for row in m.rows() {
+            log::debug!("{}", row.iter().join(""));
         }
     }
 }
 use shortest_path::*;
 
-pub fn day18(data: &str, p1: bool) -> i64 {
-    let mut positions = data
-        .lines()
-        .filter_map(|l| {
-            let (x, y) = l.split_once(',')?;
-            Some((x.trim().parse::<u32>().ok()?, y.trim().parse::<u32>().ok()?))
-        })
-        .collect_vec();
-
-    let size: u32 = 71;
-    let start = Vec2(0, 0);
-    let end = Vec2(size as i32 - 1, size as i32 - 1);
-
-    let mut obst_map: Vec<Loc<DeadEnd>> =
-        std::iter::repeat_n(Loc::Empty, (size * size) as usize).collect_vec();
-    for (x, y) in positions.drain(..1024) {
-        obst_map[(x + y * size) as usize] = Loc::Obstacle;
+#[derive(Clone)]
+pub struct Day18 {
+    /// The width and height of the memory space.
+    pub size: u32,
+    /// The number of bytes that fall before part 1 searches the path, all of them if the input
+    /// has fewer.
+    pub bytes: usize,
+}
+
+impl Day18 {
+    pub const DEFAULT: Self = Self {
+        size: 71,
+        bytes: 1024,
+    };
+
+    fn end(&self) -> Vec2 {
+        Vec2(self.size as i32 - 1, self.size as i32 - 1)
     }
 
-    let mut sp = ShortestPath::new(start, size as usize, size as usize);
-    sp.calc(&obst_map);
-    let steps = sp.steps_to(end).unwrap();
-
-    let mut path_map = obst_map.clone();
-    sp.fill_path(&mut path_map, end);
-    if p1 {
-        print_map(&path_map, size as usize, size as usize);
-        return steps as i64;
-    } else {
-        let mut prev_path_map = Vec::new();
+    /// Drop the first [`Self::bytes`] onto the memory space and find the shortest path through it.
+    ///
+    /// Returns the obstacle map, the path finder and the map filled with the shortest path.
+    fn drop_first_bytes(
+        &self,
+        positions: &[(u32, u32)],
+    ) -> (Grid<Loc<DeadEnd>>, ShortestPath, Grid<Loc<DeadEnd>>) {
+        let size = self.size as usize;
+        let mut obst_map = Grid::new(size, size, Loc::Empty);
+        for &(x, y) in positions.get(..self.bytes).unwrap_or(positions) {
+            obst_map[Vec2(x as i32, y as i32)] = Loc::Obstacle;
+        }
+
+        let mut sp = ShortestPath::new(Vec2(0, 0), size, size);
+        sp.calc(&obst_map);
+
+        let mut path_map = obst_map.clone();
+        sp.fill_path(&mut path_map, self.end());
+        (obst_map, sp, path_map)
+    }
+}
+
+impl Solution for Day18 {
+    type Input = Vec<(u32, u32)>;
+
+    aoc_common::params!(size, bytes);
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        data.lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                let (x, y) = l
+                    .split_once(',')
+                    .ok_or_else(|| parse::error(data, l, "expected a position `<x>,<y>`"))?;
+                let pos = (
+                    parse::from_str::<u32>(data, x.trim())?,
+                    parse::from_str::<u32>(data, y.trim())?,
+                );
+                if pos.0 >= self.size || pos.1 >= self.size {
+                    let msg = format!("outside of the memory space of size {}", self.size);
+                    return Err(parse::error(data, l, msg));
+                }
+                Ok(pos)
+            })
+            .try_collect()
+    }
+
+    fn part1(&self, positions: &Self::Input) -> Answer {
+        let (_, sp, path_map) = self.drop_first_bytes(positions);
+        let steps = sp
+            .steps_to(self.end())
+            .expect("the exit can't be reached after the first bytes fell");
+
+        print_map(&path_map);
+        steps.into()
+    }
+
+    fn part2(&self, positions: &Self::Input) -> Answer {
+        let end = self.end();
+        let (mut obst_map, mut sp, mut path_map) = self.drop_first_bytes(positions);
+
+        let mut prev_path_map = path_map.clone();
         let mut final_pos = None;
-        for (x, y) in positions.drain(..) {
-            let idx = (x + y * size) as usize;
-            obst_map[idx] = Loc::Obstacle;
+        for &(x, y) in positions.get(self.bytes..).unwrap_or_default() {
+            let pos = Vec2(x as i32, y as i32);
+            obst_map[pos] = Loc::Obstacle;
 
             // If the previous path gets obstructed, find a new path.
             // When there is no path anymore, that obstruction position is the answer.
-            if path_map[idx] == Loc::Path {
+            if path_map[pos] == Loc::Path {
                 prev_path_map = path_map;
 
                 sp.calc(&obst_map);
@@ -231,16 +275,15 @@ pub fn day18(data: &str, p1: bool) -> i64 {
 
                 let maybe_steps = sp.steps_to(end);
                 if maybe_steps.is_none() {
-                    prev_path_map[idx] = Loc::Custom(DeadEnd);
+                    prev_path_map[pos] = Loc::Custom(DeadEnd);
                     final_pos = Some((x, y));
                     break;
                 }
             }
         }
 
-        print_map(&prev_path_map, size as usize, size as usize);
-        println!("answer = {final_pos:?}");
+        print_map(&prev_path_map);
 
-        return 0;
-    };
+        final_pos.expect("the exit never gets blocked").into()
+    }
 }
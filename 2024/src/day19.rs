@@ -1,97 +1,111 @@
 use std::collections::HashMap;
 
+use anyhow::Context;
+use aoc_common::{Answer, Solution};
 use itertools::Itertools;
 
-pub fn day19(data: &str, p1: bool) -> i64 {
-    let mut lines = data.lines();
-    let mut proto = lines
-        .next()
-        .unwrap()
-        .split(',')
-        .map(|p| p.trim())
-        .collect_vec();
+fn count_combs<'a>(
+    des: &'a str,
+    protos: &[String],
+    stop_at_first: bool,
+    mem: &mut HashMap<&'a [u8], usize>,
+) -> usize {
+    let mut result = 0_usize;
+    let mut indices = vec![0_usize];
+    let mut des_stack = Vec::with_capacity(protos.len());
+    let mut counts = vec![0_usize];
+    des_stack.push(des.as_bytes());
+    while let (Some(des), Some(idx)) = (des_stack.last(), indices.last_mut()) {
+        if let Some(v) = mem.get(des) {
+            result += v;
+            des_stack.pop();
+            indices.pop();
+            counts.pop();
+            continue;
+        }
 
-    let patterns = lines
-        .filter_map(|p| {
-            let p = p.trim();
-            if p.is_empty() {
-                return None;
-            }
-            Some(p)
-        })
-        .collect_vec();
+        let found = protos[*idx..].iter().enumerate().find_map(|(i, p)| {
+            let rest = des.strip_prefix(p.as_bytes())?;
+            Some((i, rest))
+        });
+
+        match found {
+            Some((i, rest)) => {
+                *idx += i + 1;
 
-    // Sort by maximum length and then alphabetically.
-    proto.sort_by(|a, b| (b.len(), a).cmp(&(a.len(), b)));
-    proto.dedup();
+                if rest.is_empty() {
+                    if stop_at_first {
+                        return 1;
+                    } else {
+                        result += 1;
+                        continue;
+                    }
+                } else {
+                    des_stack.push(rest);
+                    indices.push(0);
+                    counts.push(result);
+                }
+            }
+            None => {
+                let diff_count = result - counts.pop().unwrap();
+                mem.insert(des, diff_count);
 
-    fn count_combs<'a>(
-        des: &'a str,
-        protos: &[&str],
-        stop_at_first: bool,
-        mem: &mut HashMap<&'a [u8], usize>,
-    ) -> usize {
-        let mut result = 0_usize;
-        let mut indices = vec![0_usize];
-        let mut des_stack = Vec::with_capacity(protos.len());
-        let mut counts = vec![0_usize];
-        des_stack.push(des.as_bytes());
-        while let (Some(des), Some(idx)) = (des_stack.last(), indices.last_mut()) {
-            if let Some(v) = mem.get(des) {
-                result += v;
                 des_stack.pop();
                 indices.pop();
-                counts.pop();
-                continue;
             }
+        }
+    }
+    result
+}
 
-            let found = protos[*idx..].iter().enumerate().find_map(|(i, p)| {
-                let rest = des.strip_prefix(p.as_bytes())?;
-                Some((i, rest))
-            });
+#[derive(Clone)]
+pub struct Day19;
 
-            match found {
-                Some((i, rest)) => {
-                    *idx += i + 1;
+impl Solution for Day19 {
+    /// The towel patterns and the desired designs.
+    type Input = (Vec<String>, Vec<String>);
 
-                    if rest.is_empty() {
-                        if stop_at_first {
-                            return 1;
-                        } else {
-                            result += 1;
-                            continue;
-                        }
-                    } else {
-                        des_stack.push(rest);
-                        indices.push(0);
-                        counts.push(result);
-                    }
-                }
-                None => {
-                    let diff_count = result - counts.pop().unwrap();
-                    mem.insert(des, diff_count);
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let mut lines = data.lines();
+        let mut proto = lines
+            .next()
+            .context("expected the towel patterns on the first line")?
+            .split(',')
+            .map(|p| p.trim().to_owned())
+            .collect_vec();
 
-                    des_stack.pop();
-                    indices.pop();
+        let patterns = lines
+            .filter_map(|p| {
+                let p = p.trim();
+                if p.is_empty() {
+                    return None;
                 }
-            }
-        }
-        result
+                Some(p.to_owned())
+            })
+            .collect_vec();
+
+        // Sort by maximum length and then alphabetically.
+        proto.sort_by(|a, b| (b.len(), a).cmp(&(a.len(), b)));
+        proto.dedup();
+
+        Ok((proto, patterns))
     }
 
-    let result = if p1 {
+    fn part1(&self, (proto, patterns): &Self::Input) -> Answer {
         patterns
             .iter()
-            .filter(|p| count_combs(p, &proto, true, &mut Default::default()) != 0)
+            .filter(|p| count_combs(p, proto, true, &mut Default::default()) != 0)
             .count()
-    } else {
-        patterns
+            .into()
+    }
+
+    fn part2(&self, (proto, patterns): &Self::Input) -> Answer {
+        let result: usize = patterns
             .iter()
             .scan(Default::default(), |state, p| {
-                Some(count_combs(p, &proto, false, state))
+                Some(count_combs(p, proto, false, state))
             })
-            .sum()
-    };
-
-    result as i64
+            .sum();
+        result.into()
+    }
 }
@@ -1,3 +1,4 @@
+use aoc_common::{parse, Answer, Solution};
 use itertools::Itertools;
 
 fn is_safe_levels(levels: impl IntoIterator<Item = u32>) -> bool {
@@ -11,29 +12,42 @@ fn is_safe_levels(levels: impl IntoIterator<Item = u32>) -> bool {
             } else {
                 true
             };
-            let is_safe_step = e_diff >= 1 && e_diff <= 3;
+            let is_safe_step = (1..=3).contains(&e_diff);
 
             (Some(e_greater), is_safe_step && always_inc_or_dec && safe)
         });
     is_safe
 }
 
-pub fn day2(data: &str, p1: bool) -> i64 {
-    let result: usize = data
-        .lines()
-        .filter_map(|l| {
-            let levels = l
-                .split(char::is_whitespace)
-                .filter_map(|n| n.parse::<u32>().ok())
-                .collect::<Vec<_>>();
-
-            if levels.is_empty() {
-                return None;
-            }
-
-            let is_safe = if p1 {
-                is_safe_levels(levels.iter().copied())
-            } else {
+#[derive(Clone)]
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        data.lines()
+            .map(|l| {
+                l.split_whitespace()
+                    .map(|n| parse::from_str::<u32>(data, n))
+                    .try_collect::<_, Vec<_>, _>()
+            })
+            .filter_ok(|levels| !levels.is_empty())
+            .try_collect()
+    }
+
+    fn part1(&self, reports: &Self::Input) -> Answer {
+        reports
+            .iter()
+            .filter(|levels| is_safe_levels(levels.iter().copied()))
+            .count()
+            .into()
+    }
+
+    fn part2(&self, reports: &Self::Input) -> Answer {
+        reports
+            .iter()
+            .filter(|levels| {
                 let safe = is_safe_levels(levels.iter().copied());
                 if levels.len() <= 1 || safe {
                     true
@@ -50,10 +64,8 @@ pub fn day2(data: &str, p1: bool) -> i64 {
                     }
                     safe
                 }
-            };
-            Some(is_safe as usize)
-        })
-        .sum();
-
-    result as i64
+            })
+            .count()
+            .into()
+    }
 }
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
+use aoc_common::{grid::Grid, vec2::Vec2, Answer, Solution};
 use itertools::Itertools;
 
-use crate::{day12::Vec2, day18::shortest_path::*};
+use crate::day18::shortest_path::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Ext {
@@ -27,63 +28,65 @@ struct Cheat {
     saved_steps: i32,
 }
 
-pub fn day20(data: &str, p1: bool) -> i64 {
-    let mut start = Vec2(0, 0);
-    let mut end = Vec2(0, 0);
-
-    let map = data
-        .lines()
-        .filter(|l| !l.is_empty())
-        .enumerate()
-        .map(|(y, l)| {
-            l.chars()
-                .enumerate()
-                .map(|(x, c)| match c {
-                    '#' => Loc::Obstacle,
-                    '.' => Loc::Empty,
-                    'S' => {
-                        start = Vec2(x as i32, y as i32);
-                        Loc::Empty
-                    }
-                    'E' => {
-                        end = Vec2(x as i32, y as i32);
-                        Loc::Empty
-                    }
-                    _ => unreachable!(),
-                })
-                .collect_vec()
-        })
-        .collect_vec();
-    let height = map.len();
-    let width = map.first().unwrap().len();
-    assert!(map.iter().all(|l| l.len() == width));
-    let map = map.into_iter().flatten().collect_vec();
+pub struct RaceTrack {
+    map: Grid<Loc>,
+    start: Vec2,
+    end: Vec2,
+}
 
-    let mut sp = ShortestPath::new(start, width, height);
-    sp.calc(&map);
-    let normal_steps = sp.steps_to(end).unwrap();
+#[derive(Clone)]
+pub struct Day20 {
+    /// The minimum amount of steps a counted cheat must save.
+    pub min_saved: i32,
+}
 
-    println!("steps without cheats: {normal_steps}");
+impl Day20 {
+    pub const DEFAULT: Self = Self { min_saved: 100 };
+}
 
-    let mut path_map = map.clone();
-    sp.fill_path(&mut path_map, end);
+impl Solution for Day20 {
+    type Input = RaceTrack;
+
+    aoc_common::params!(min_saved);
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let (map, [start, end]) = Grid::parse(data, ['S', 'E'], |c| match c {
+            '#' => Some(Loc::Obstacle),
+            '.' | 'S' | 'E' => Some(Loc::Empty),
+            _ => None,
+        })?;
 
-    // Print path map with start and end markers.
-    {
-        let mut path_map = path_map.clone();
-        path_map[start.to_idx(width)] = Loc::Custom(Ext::Start);
-        path_map[end.to_idx(width)] = Loc::Custom(Ext::End);
-        print_map(&path_map, width, height);
+        Ok(RaceTrack { map, start, end })
     }
 
-    let mut cheats = HashMap::<i32, Vec<Cheat>>::new();
-    let path = sp.get_path(end);
+    fn part1(&self, track: &Self::Input) -> Answer {
+        let RaceTrack { map, start, end } = track;
+        let (start, end) = (*start, *end);
+
+        let mut sp = ShortestPath::new(start, map.width(), map.height());
+        sp.calc(map);
+        let normal_steps = sp.steps_to(end).unwrap();
+
+        log::info!("steps without cheats: {normal_steps}");
+
+        let mut path_map = map.clone();
+        sp.fill_path(&mut path_map, end);
+
+        // Print path map with start and end markers.
+        {
+            let mut path_map = path_map.clone();
+            path_map[start] = Loc::Custom(Ext::Start);
+            path_map[end] = Loc::Custom(Ext::End);
+            print_map(&path_map);
+        }
+
+        let mut cheats = HashMap::<i32, Vec<Cheat>>::new();
+        let path = sp.get_path(end);
 
-    let result = if p1 {
         for (p_start, steps_start) in path {
             let viable_cheats = p_start
-                .neighbors()
-                .flat_map(|(p_middle, _)| p_middle.neighbors().map(move |(p, _)| (p_middle, p)))
+                .neighbors4()
+                .flat_map(|p_middle| p_middle.neighbors4().map(move |p| (p_middle, p)))
                 .filter_map(|(p_middle, p_end)| {
                     let steps = sp.steps_to(p_end)?;
                     // Saved steps are: the amount of steps from start -> end,
@@ -108,41 +111,44 @@ pub fn day20(data: &str, p1: bool) -> i64 {
             .collect_vec();
 
         let c = &cheats[&overview.last().unwrap().0][0];
-        println!();
-        println!(
+        log::info!(
             "best cheat: {} steps ({} saved)",
             normal_steps as i32 - c.saved_steps,
             c.saved_steps
         );
-        show_cheat(&map, &c, width, height, start, end);
+        show_cheat(map, c, start, end);
 
         let result: usize = overview
             .iter()
-            .skip_while(|(s, _)| *s != 100)
+            .skip_while(|(s, _)| *s < self.min_saved)
             .map(|(_, count)| *count)
             .sum();
-        result
-    } else {
-        unimplemented!("problem 2")
-    };
+        result.into()
+    }
 
-    result as i64
+    fn part2(&self, _track: &Self::Input) -> Answer {
+        unimplemented!("problem 2")
+    }
 }
 
-fn show_cheat(map: &Vec<Loc>, c: &Cheat, width: usize, height: usize, start: Vec2, end: Vec2) {
+fn show_cheat(map: &Grid<Loc>, c: &Cheat, start: Vec2, end: Vec2) {
+    if !log::log_enabled!(log::Level::Debug) {
+        return;
+    }
+
     let mut path_map = map.clone();
-    for p in &c.path {
-        path_map[p.to_idx(width)] = Loc::Empty;
+    for &p in &c.path {
+        path_map[p] = Loc::Empty;
     }
 
-    let mut sp = ShortestPath::new(start, width, height);
+    let mut sp = ShortestPath::new(start, map.width(), map.height());
     sp.calc(&path_map);
     sp.fill_path(&mut path_map, end);
-    for p in &c.path {
-        path_map[p.to_idx(width)] = Loc::Custom(Ext::ShortCut);
+    for &p in &c.path {
+        path_map[p] = Loc::Custom(Ext::ShortCut);
     }
-    path_map[start.to_idx(width)] = Loc::Custom(Ext::Start);
-    path_map[end.to_idx(width)] = Loc::Custom(Ext::End);
+    path_map[start] = Loc::Custom(Ext::Start);
+    path_map[end] = Loc::Custom(Ext::End);
 
-    print_map(&path_map, width, height);
+    print_map(&path_map);
 }
@@ -1,8 +1,8 @@
-use super::day18::shortest_path::{self, Dir, Vec2};
+use aoc_common::{dir::Dir4, parse, vec2::Vec2, Answer, Solution};
 use itertools::Itertools;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-enum NumPadBtn {
+pub enum NumPadBtn {
     Num(u8),
     A,
 }
@@ -68,14 +68,11 @@ pub mod dijkstra {
             let mut end_nodes = HashMap::new();
             let mut last_end_dist = None;
 
-            loop {
-                let Some((idx, _)) = candidates
-                    .iter()
-                    .enumerate()
-                    .min_by_key(|(_, (_, dist))| dist)
-                else {
-                    break;
-                };
+            while let Some((idx, _)) = candidates
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (_, dist))| dist)
+            {
                 let (node, dist) = candidates.swap_remove(idx);
                 // The node must be already in the graph, since it is a candidate.
                 let (_, visited, _) = graph.get_mut(&node).unwrap();
@@ -160,7 +157,7 @@ pub mod dijkstra {
                         ..
                     } = self;
 
-                    let (k, v) = this.graph.get_key_value(&end)?;
+                    let (k, v) = this.graph.get_key_value(end)?;
                     let dist = v.0;
                     let mut path = vec![k];
 
@@ -239,8 +236,8 @@ impl NumPadBtn {
         Self::BTN_TO_POS_LUT[self.to_idx()]
     }
 
-    fn apply(self, dir: Dir) -> Option<Self> {
-        let pos = self.to_pos().offset_vec(dir.into_vec2());
+    fn apply(self, dir: Dir4) -> Option<Self> {
+        let pos = self.to_pos() + dir.to_vec2();
         let (i, _) = Self::BTN_TO_POS_LUT
             .iter()
             .enumerate()
@@ -251,7 +248,7 @@ impl NumPadBtn {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum DirPadBtn {
-    Dir(Dir),
+    Dir(Dir4),
     A,
 }
 
@@ -259,17 +256,14 @@ impl std::fmt::Display for DirPadBtn {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let c = match self {
             Self::A => 'A',
-            Self::Dir(Dir::Up) => '^',
-            Self::Dir(Dir::Right) => '>',
-            Self::Dir(Dir::Down) => 'v',
-            Self::Dir(Dir::Left) => '<',
+            Self::Dir(d) => d.glyph(),
         };
         write!(f, "{c}")
     }
 }
 
 impl DirPadBtn {
-    fn dir(self) -> Dir {
+    fn dir(self) -> Dir4 {
         match self {
             Self::Dir(d) => d,
             _ => panic!("not a dir"),
@@ -277,22 +271,17 @@ impl DirPadBtn {
     }
 
     fn to_idx(self) -> usize {
-        use shortest_path::Dir::*;
-        use DirPadBtn::*;
         match self {
-            Dir(Up) => 0,
-            Dir(Right) => 1,
-            Dir(Down) => 2,
-            Dir(Left) => 3,
-            A => 4,
+            Self::Dir(d) => d as usize,
+            Self::A => 4,
         }
     }
 
     const ALL: [DirPadBtn; 5] = [
-        Self::Dir(Dir::Up),
-        Self::Dir(Dir::Right),
-        Self::Dir(Dir::Down),
-        Self::Dir(Dir::Left),
+        Self::Dir(Dir4::Up),
+        Self::Dir(Dir4::Right),
+        Self::Dir(Dir4::Down),
+        Self::Dir(Dir4::Left),
         Self::A,
     ];
     const BTN_TO_POS_LUT: [Vec2; 5] = [
@@ -307,8 +296,8 @@ impl DirPadBtn {
         Self::BTN_TO_POS_LUT[self.to_idx()]
     }
 
-    fn apply(self, dir: Dir) -> Option<Self> {
-        let pos = self.to_pos().offset_vec(dir.into_vec2());
+    fn apply(self, dir: Dir4) -> Option<Self> {
+        let pos = self.to_pos() + dir.to_vec2();
         let (i, _) = Self::BTN_TO_POS_LUT
             .iter()
             .enumerate()
@@ -317,98 +306,122 @@ impl DirPadBtn {
     }
 }
 
-pub fn day21(data: &str, _p1: bool) -> i64 {
-    let codes = data
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            let n = l
-                .trim_matches(|c: char| !c.is_numeric())
-                .parse::<u32>()
-                .ok();
-            let code = l
-                .chars()
-                .map(|c| {
-                    if let Some(d) = c.to_digit(10) {
-                        NumPadBtn::Num(d as u8)
-                    } else if c == 'A' {
-                        NumPadBtn::A
-                    } else {
-                        unreachable!("{c} is invalid");
-                    }
-                })
-                .collect_vec();
-            (n, code)
-        })
-        .collect_vec();
-
-    fn shortest_seq(code: &Vec<NumPadBtn>) -> String {
-        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-        struct SeqNode<'a>(NumPadBtn, DirPadBtn, DirPadBtn, DirPadBtn, &'a [NumPadBtn]);
-
-        impl<'a> SeqNode<'a> {
-            fn step(&self, n: DirPadBtn) -> Option<SeqNode<'a>> {
-                let SeqNode(mut num, mut dir1, mut dir2, _, mut s) = self.clone();
-
-                if n == DirPadBtn::A {
-                    if dir2 == DirPadBtn::A {
-                        if dir1 == DirPadBtn::A {
-                            if num == *s.get(0)? {
-                                s = &s[1..];
-                            } else {
-                                return None;
-                            }
-                        } else {
-                            num = num.apply(dir1.dir())?;
-                        }
-                    } else {
-                        dir1 = dir1.apply(dir2.dir())?;
-                    }
-                } else {
-                    dir2 = dir2.apply(n.dir())?;
+/// The shortest sequence of button presses on the directional keypad of the human that types
+/// `code`, with `layers` robots using directional keypads in between.
+fn shortest_seq(code: &[NumPadBtn], layers: usize) -> String {
+    /// The arm of the numeric keypad robot, the arms of the directional keypad robots (starting
+    /// with the one controlling the numeric keypad robot), the last pressed button of the human,
+    /// and the rest of the code.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct SeqNode<'a>(NumPadBtn, Vec<DirPadBtn>, DirPadBtn, &'a [NumPadBtn]);
+
+    impl<'a> SeqNode<'a> {
+        fn step(&self, n: DirPadBtn) -> Option<SeqNode<'a>> {
+            let SeqNode(mut num, mut dirs, _, mut s) = self.clone();
+
+            // An `A` press goes through to the next layer, any other press moves the arm of the
+            // next layer.
+            let mut press = n;
+            for dir in dirs.iter_mut().rev() {
+                if press != DirPadBtn::A {
+                    *dir = dir.apply(press.dir())?;
+                    return Some(SeqNode(num, dirs, n, s));
                 }
+                press = *dir;
+            }
 
-                Some(SeqNode(num, dir1, dir2, n, s))
+            if press != DirPadBtn::A {
+                num = num.apply(press.dir())?;
+            } else if num == *s.first()? {
+                s = &s[1..];
+            } else {
+                return None;
             }
+
+            Some(SeqNode(num, dirs, n, s))
         }
+    }
 
-        impl<'a> dijkstra::Node for SeqNode<'a> {
-            fn neighbors<'b>(&'b self, _: &()) -> impl Iterator<Item = (SeqNode<'a>, i32)>
-            where
-                Self: 'a,
-            {
-                let v = DirPadBtn::ALL
-                    .into_iter()
-                    .filter_map(move |d| Some((self.step(d)?, 1)))
-                    .collect_vec();
-                v.into_iter()
-            }
+    impl<'a> dijkstra::Node for SeqNode<'a> {
+        fn neighbors<'b>(&'b self, _: &()) -> impl Iterator<Item = (SeqNode<'a>, i32)>
+        where
+            Self: 'a,
+        {
+            let v = DirPadBtn::ALL
+                .into_iter()
+                .filter_map(move |d| Some((self.step(d)?, 1)))
+                .collect_vec();
+            v.into_iter()
         }
+    }
 
-        use dijkstra::*;
-        let mut sp = ShortestPath::new(SeqNode(
-            NumPadBtn::A,
-            DirPadBtn::A,
-            DirPadBtn::A,
-            DirPadBtn::A,
-            code as &[NumPadBtn],
-        ));
-        let n = sp.calc(&(), |n: &SeqNode, _| n.4.len() == 0);
+    use dijkstra::*;
+    let mut sp = ShortestPath::new(SeqNode(
+        NumPadBtn::A,
+        vec![DirPadBtn::A; layers],
+        DirPadBtn::A,
+        code,
+    ));
+    let n = sp.calc(&(), |n: &SeqNode, _| n.3.is_empty());
 
-        let (p, _dist) = sp.paths(&n).next().unwrap();
+    let (p, _dist) = sp.paths(&n).next().unwrap();
 
-        let seq = p.iter().map(|n| n.3).skip(1).join("");
+    p.iter().map(|n| n.2).skip(1).join("")
+}
+
+#[derive(Clone)]
+pub struct Day21 {
+    /// The number of robots using directional keypads between the human and the numeric keypad
+    /// robot.
+    pub layers: usize,
+}
 
-        seq
+impl Day21 {
+    pub const DEFAULT: Self = Self { layers: 2 };
+}
+
+impl Solution for Day21 {
+    type Input = Vec<(Option<u32>, Vec<NumPadBtn>)>;
+
+    aoc_common::params!(layers);
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        data.lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                let n = l
+                    .trim_matches(|c: char| !c.is_numeric())
+                    .parse::<u32>()
+                    .ok();
+                let code = parse::chars(l)
+                    .map(|(c, at)| {
+                        if let Some(d) = c.to_digit(10) {
+                            Ok(NumPadBtn::Num(d as u8))
+                        } else if c == 'A' {
+                            Ok(NumPadBtn::A)
+                        } else {
+                            Err(parse::error(data, at, format!("'{c}' is not a button")))
+                        }
+                    })
+                    .try_collect()?;
+                Ok((n, code))
+            })
+            .try_collect()
     }
 
-    let mut result = 0;
-    for (n, code) in codes {
-        let code = shortest_seq(&code);
-        let compl = code.len() as u32 * n.unwrap_or(0);
-        println!("len={}, {}: {code}", code.len(), compl);
-        result += compl;
+    fn part1(&self, codes: &Self::Input) -> Answer {
+        let mut result = 0;
+        for (n, code) in codes {
+            let code = shortest_seq(code, self.layers);
+            let compl = code.len() as u32 * n.unwrap_or(0);
+            log::debug!("len={}, {}: {code}", code.len(), compl);
+            result += compl;
+        }
+
+        result.into()
     }
 
-    result as i64
+    fn part2(&self, _codes: &Self::Input) -> Answer {
+        unimplemented!("part 2")
+    }
 }
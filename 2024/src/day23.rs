@@ -3,8 +3,8 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+use aoc_common::{parse, Answer, Solution};
 use itertools::Itertools;
-use tqdm::Iter;
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Set<T, const N: usize>([T; N]);
@@ -42,23 +42,30 @@ impl<T, const N: usize> DerefMut for Set<T, N> {
     }
 }
 
-pub fn day23(data: &str, p1: bool) -> i64 {
-    let cons = data
-        .lines()
-        .filter(|l| !l.is_empty())
-        .filter_map(|l| Some(l.split_once('-').unwrap()))
-        .collect_vec();
+#[derive(Clone)]
+pub struct Day23;
 
-    let mut map = HashMap::<&str, HashSet<&str>>::new();
+impl Solution for Day23 {
+    /// The connections of each computer.
+    type Input = HashMap<String, HashSet<String>>;
 
-    for (a, b) in &cons {
-        map.entry(a).or_default().insert(b);
-        map.entry(b).or_default().insert(a);
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let mut map = HashMap::<String, HashSet<String>>::new();
+
+        for l in data.lines().filter(|l| !l.is_empty()) {
+            let (a, b) = l
+                .split_once('-')
+                .ok_or_else(|| parse::error(data, l, "expected a connection `<a>-<b>`"))?;
+            map.entry(a.to_owned()).or_default().insert(b.to_owned());
+            map.entry(b.to_owned()).or_default().insert(a.to_owned());
+        }
+
+        Ok(map)
     }
 
-    if p1 {
+    fn part1(&self, map: &Self::Input) -> Answer {
         let mut threes = HashSet::new();
-        for (k, v) in &map {
+        for (k, v) in map {
             for v in v {
                 for v2 in map
                     .get(v)
@@ -66,7 +73,7 @@ pub fn day23(data: &str, p1: bool) -> i64 {
                     .flatten()
                     .filter(|v2| map.get(*v2).is_some_and(|v2_cons| v2_cons.contains(k)))
                 {
-                    threes.insert(Set::new([*k, *v, *v2]));
+                    threes.insert(Set::new([k, v, v2]));
                 }
             }
         }
@@ -77,33 +84,32 @@ pub fn day23(data: &str, p1: bool) -> i64 {
             .sorted()
             .collect_vec();
 
-        return result.len() as i64;
+        result.len().into()
     }
 
-    let mut largest_component = HashSet::new();
-    for (&a, b) in map.iter().tqdm() {
-        let mut comp = HashSet::new();
-
-        comp.insert(a);
-        let mut curr_path = vec![b];
-        while let Some(b) = curr_path.pop() {
-            for b in b {
-                let Some(m) = map.get(b) else { continue };
-
-                if comp.iter().all(|a| m.contains(a)) {
-                    comp.insert(b);
-                    curr_path.push(m)
+    fn part2(&self, map: &Self::Input) -> Answer {
+        let mut largest_component = HashSet::new();
+        for (a, b) in map.iter() {
+            let mut comp = HashSet::new();
+
+            comp.insert(a);
+            let mut curr_path = vec![b];
+            while let Some(b) = curr_path.pop() {
+                for b in b {
+                    let Some(m) = map.get(b) else { continue };
+
+                    if comp.iter().all(|a| m.contains(*a)) {
+                        comp.insert(b);
+                        curr_path.push(m)
+                    }
                 }
             }
-        }
 
-        if comp.len() > largest_component.len() {
-            largest_component = comp;
+            if comp.len() > largest_component.len() {
+                largest_component = comp;
+            }
         }
-    }
 
-    let pwd = largest_component.iter().sorted().join(",");
-    println!("password: {pwd}");
-
-    largest_component.len() as i64
+        largest_component.iter().sorted().join(",").into()
+    }
 }
@@ -1,26 +1,26 @@
 use std::collections::{HashMap, VecDeque};
 
+use aoc_common::{parse, Answer, Solution};
 use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, one_of, space1},
+    combinator::{map, value},
+    error::context,
+    sequence::{separated_pair, terminated, tuple},
+};
 
 use super::day23::Set;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Op {
+pub enum Op {
     And,
     Or,
     Xor,
 }
 
 impl Op {
-    fn from_str(s: &str) -> Option<Op> {
-        Some(match s {
-            "AND" => Self::And,
-            "OR" => Self::Or,
-            "XOR" => Self::Xor,
-            _ => return None,
-        })
-    }
-
     fn apply(self, lhs: bool, rhs: bool) -> bool {
         match self {
             Self::And => lhs && rhs,
@@ -30,45 +30,81 @@ impl Op {
     }
 }
 
-pub fn day24(data: &str, p1: bool) -> i64 {
-    let mut lines = data.lines();
+pub struct Device {
+    /// The initial wire states.
+    states: HashMap<String, bool>,
+    /// The gates as (lhs wire, operation, rhs wire, output wire).
+    gates: Vec<(String, Op, String, String)>,
+}
 
-    let mut states: HashMap<&str, bool> = (&mut lines)
-        .take_while(|l| !l.is_empty())
-        .filter_map(|l| {
-            let (name, num) = l.split_once(":")?;
-            Some((name.trim(), num.trim().parse::<u8>().ok().unwrap() != 0))
-        })
-        .collect();
+#[derive(Clone)]
+pub struct Day24;
 
-    let ops = lines
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            let (lhs, op, rhs, _arrow, out) = l.split(' ').collect_tuple().unwrap();
-            (lhs, Op::from_str(op.trim()).unwrap(), rhs, out)
-        })
-        .collect_vec();
+impl Solution for Day24 {
+    type Input = Device;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let state = parse::key_value(alphanumeric1, one_of("01"));
+        let state = context(
+            "wire state",
+            map(state, |(name, state)| (name, state == '1')),
+        );
 
-    let mut map = HashMap::<&str, Vec<(&str, (Op, &str))>>::new();
-    for (lhs, op, rhs, out) in ops {
-        map.entry(lhs).or_default().push((rhs, (op, out)));
-        map.entry(rhs).or_default().push((lhs, (op, out)));
+        let op = alt((
+            value(Op::And, tag("AND")),
+            value(Op::Or, tag("OR")),
+            value(Op::Xor, tag("XOR")),
+        ));
+        let gate = tuple((
+            terminated(alphanumeric1, space1),
+            terminated(context("AND, OR or XOR", op), space1),
+            terminated(alphanumeric1, tag(" -> ")),
+            alphanumeric1,
+        ));
+        let gate = context("gate", gate);
+
+        let (states, gates) = parse::all(
+            data,
+            separated_pair(
+                parse::lines(state),
+                parse::section_break,
+                parse::lines(gate),
+            ),
+        )?;
+
+        Ok(Device {
+            states: states
+                .into_iter()
+                .map(|(name, state)| (name.to_owned(), state))
+                .collect(),
+            gates: gates
+                .into_iter()
+                .map(|(lhs, op, rhs, out)| (lhs.to_owned(), op, rhs.to_owned(), out.to_owned()))
+                .collect(),
+        })
     }
 
-    if p1 {
+    fn part1(&self, device: &Self::Input) -> Answer {
+        let mut states: HashMap<&str, bool> = device
+            .states
+            .iter()
+            .map(|(k, v)| (k.as_str(), *v))
+            .collect();
+
+        let mut map = HashMap::<&str, Vec<(&str, (Op, &str))>>::new();
+        for (lhs, op, rhs, out) in &device.gates {
+            let (lhs, rhs, out) = (lhs.as_str(), rhs.as_str(), out.as_str());
+            map.entry(lhs).or_default().push((rhs, (*op, out)));
+            map.entry(rhs).or_default().push((lhs, (*op, out)));
+        }
+
         let mut paths = VecDeque::<(Set<&str, 2>, Op, &str)>::new();
-        paths.extend(
-            states
-                .keys()
-                .filter_map(|k| {
-                    Some(
-                        map.get(k)?
-                            .iter()
-                            .map(|(k2, (op, out))| (Set::new([*k, *k2]), *op, *out)),
-                    )
-                })
-                .flatten(),
-        );
+        paths.extend(states.keys().flat_map(|k| {
+            map.get(k)
+                .into_iter()
+                .flatten()
+                .map(|(k2, (op, out))| (Set::new([*k, *k2]), *op, *out))
+        }));
 
         while let Some(ref p @ (ref item, ref op, out)) = paths.pop_front() {
             if states.contains_key(out) {
@@ -87,7 +123,7 @@ pub fn day24(data: &str, p1: bool) -> i64 {
 
             let out_state = op.apply(a_state, b_state);
             states.insert(out, out_state);
-            println!("{item:?} -> {out} = {out_state}");
+            log::trace!("{item:?} -> {out} = {out_state}");
 
             let Some(m) = map.get(out) else { continue };
 
@@ -103,14 +139,14 @@ pub fn day24(data: &str, p1: bool) -> i64 {
             .collect_vec();
 
         for (z, val) in &zs {
-            println!("{z} = {val}");
+            log::debug!("{z} = {val}");
         }
 
         let result: u64 = zs
             .iter()
             .map(|(k, v)| {
                 (
-                    k.trim_matches(|c: char| !c.is_digit(10))
+                    k.trim_matches(|c: char| !c.is_ascii_digit())
                         .parse::<u32>()
                         .unwrap(),
                     v,
@@ -119,8 +155,10 @@ pub fn day24(data: &str, p1: bool) -> i64 {
             .map(|(k, &&v)| (v as u64) << (k as u64))
             .sum();
 
-        return result as i64;
+        result.into()
     }
 
-    unimplemented!("part 2");
+    fn part2(&self, _device: &Self::Input) -> Answer {
+        unimplemented!("part 2");
+    }
 }
@@ -1,36 +1,57 @@
+use aoc_common::{parse, Answer, Solution};
 use itertools::Itertools;
 
-pub fn day25(data: &str, _p1: bool) -> i64 {
-    let lines = data.lines().collect_vec();
+#[derive(Clone)]
+pub struct Day25;
 
-    let (keys, locks): (Vec<_>, Vec<_>) = lines
-        .split(|l| l.is_empty())
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            let is_key = l[0].chars().all(|c| c == '.');
+impl Solution for Day25 {
+    /// The column heights of all keys and locks.
+    type Input = (Vec<[u8; 5]>, Vec<[u8; 5]>);
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let lines = data.lines().collect_vec();
+
+        let mut keys = vec![];
+        let mut locks = vec![];
+        for schematic in lines.split(|l| l.is_empty()).filter(|l| !l.is_empty()) {
+            let expected = "expected a schematic of 7 lines with 5 `#` or `.`";
+            if schematic.len() != 7 {
+                return Err(parse::error(data, schematic[0], expected));
+            }
+            if let Some(l) = schematic.iter().find(|l| l.len() != 5) {
+                return Err(parse::error(data, l, expected));
+            }
+
+            let is_key = schematic[0].chars().all(|c| c == '.');
             let mut cols = [0_u8; 5];
 
-            for l in &l[1..6] {
-                for (i, c) in l.chars().enumerate().take(5) {
+            for l in &schematic[1..6] {
+                for (i, c) in l.chars().enumerate() {
                     cols[i] += (c == '#') as u8;
                 }
             }
 
             if is_key {
-                (Some(cols), None)
+                keys.push(cols);
             } else {
-                (None, Some(cols))
+                locks.push(cols);
             }
-        })
-        .unzip();
-    let keys = keys.into_iter().flatten().collect_vec();
-    let locks = locks.into_iter().flatten().collect_vec();
-
-    let matching_pairs = keys
-        .iter()
-        .flat_map(|k| locks.iter().map(move |l| (k, l)))
-        .filter(|(k, l)| k.iter().zip(l.iter()).all(|(&k, &l)| (k + l) <= 5))
-        .collect_vec();
-
-    matching_pairs.len() as i64
+        }
+
+        Ok((keys, locks))
+    }
+
+    fn part1(&self, (keys, locks): &Self::Input) -> Answer {
+        let matching_pairs = keys
+            .iter()
+            .flat_map(|k| locks.iter().map(move |l| (k, l)))
+            .filter(|(k, l)| k.iter().zip(l.iter()).all(|(&k, &l)| (k + l) <= 5))
+            .collect_vec();
+
+        matching_pairs.len().into()
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Answer {
+        unimplemented!("day 25 has no part 2")
+    }
 }
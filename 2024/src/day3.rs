@@ -1,3 +1,5 @@
+use aoc_common::{Answer, Solution};
+
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
 enum Inst {
     Do,
@@ -5,10 +7,19 @@ enum Inst {
     Mul,
 }
 
-const KEYWORDS: [&'static str; 3] = ["don't", "do", "mul"];
+const KEYWORDS: [&str; 3] = ["don't", "do", "mul"];
+
+#[derive(Clone)]
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = String;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        Ok(data.to_owned())
+    }
 
-pub fn day3(data: &str, p1: bool) -> i64 {
-    if p1 {
+    fn part1(&self, data: &Self::Input) -> Answer {
         let result: isize = data
             .split("mul")
             .skip(1)
@@ -32,79 +43,79 @@ pub fn day3(data: &str, p1: bool) -> i64 {
                 Some(lhs * rhs)
             })
             .sum();
-        return result as i64;
+        result.into()
     }
 
-    let mut chunks = Vec::<(Inst, &str)>::new();
-    let mut slice = data;
-    let mut last_inst = None;
+    fn part2(&self, data: &Self::Input) -> Answer {
+        let mut chunks = Vec::<(Inst, &str)>::new();
+        let mut slice = data.as_str();
+        let mut last_inst = None;
 
-    loop {
-        let Some((next_inst, chunk, rest)) = KEYWORDS
-            .iter()
-            .enumerate()
-            .filter_map(|(i, kw)| {
-                let Some((chunk, rest)) = slice.split_once(kw) else {
-                    return None;
-                };
+        loop {
+            let Some((next_inst, chunk, rest)) = KEYWORDS
+                .iter()
+                .enumerate()
+                .filter_map(|(i, kw)| {
+                    let (chunk, rest) = slice.split_once(kw)?;
 
-                let inst = match i {
-                    0 => Inst::Dont,
-                    1 => Inst::Do,
-                    2 => Inst::Mul,
-                    _ => unreachable!(),
-                };
+                    let inst = match i {
+                        0 => Inst::Dont,
+                        1 => Inst::Do,
+                        2 => Inst::Mul,
+                        _ => unreachable!(),
+                    };
 
-                Some((inst, chunk, rest))
-            })
-            .min_by_key(|(_, chunk, _)| chunk.len())
-        else {
+                    Some((inst, chunk, rest))
+                })
+                .min_by_key(|(_, chunk, _)| chunk.len())
+            else {
+                if let Some(inst) = last_inst.take() {
+                    chunks.push((inst, slice));
+                }
+                break;
+            };
+            slice = rest;
             if let Some(inst) = last_inst.take() {
-                chunks.push((inst, slice));
+                chunks.push((inst, chunk));
             }
-            break;
-        };
-        slice = rest;
-        if let Some(inst) = last_inst.take() {
-            chunks.push((inst, chunk));
+            last_inst = Some(next_inst);
         }
-        last_inst = Some(next_inst);
-    }
 
-    let mut mul_enabled = true;
-    let result: isize = chunks
-        .into_iter()
-        .filter_map(|(inst, chunk)| match inst {
-            Inst::Mul => {
-                let (before_brace, after_brace) = chunk.split_once('(')?;
-                if !before_brace.is_empty() {
-                    return None;
-                }
-                let (lhs, after_comma) = after_brace.split_once(',')?;
-                let (rhs, _) = after_comma.split_once(')')?;
+        let mut mul_enabled = true;
+        let result: isize = chunks
+            .into_iter()
+            .filter_map(|(inst, chunk)| match inst {
+                Inst::Mul => {
+                    let (before_brace, after_brace) = chunk.split_once('(')?;
+                    if !before_brace.is_empty() {
+                        return None;
+                    }
+                    let (lhs, after_comma) = after_brace.split_once(',')?;
+                    let (rhs, _) = after_comma.split_once(')')?;
 
-                if !mul_enabled {
-                    return None;
-                }
-                if !lhs.chars().all(|c| c.is_ascii_digit()) {
-                    return None;
-                }
-                if !rhs.chars().all(|c| c.is_ascii_digit()) {
-                    return None;
-                }
+                    if !mul_enabled {
+                        return None;
+                    }
+                    if !lhs.chars().all(|c| c.is_ascii_digit()) {
+                        return None;
+                    }
+                    if !rhs.chars().all(|c| c.is_ascii_digit()) {
+                        return None;
+                    }
 
-                let lhs = lhs.parse::<isize>().expect("must be number");
-                let rhs = rhs.parse::<isize>().expect("must be number");
+                    let lhs = lhs.parse::<isize>().expect("must be number");
+                    let rhs = rhs.parse::<isize>().expect("must be number");
 
-                Some(lhs * rhs)
-            }
-            Inst::Dont | Inst::Do => {
-                if let Some(_) = chunk.strip_prefix("()") {
-                    mul_enabled = inst == Inst::Do;
+                    Some(lhs * rhs)
                 }
-                None
-            }
-        })
-        .sum();
-    result as i64
+                Inst::Dont | Inst::Do => {
+                    if chunk.starts_with("()") {
+                        mul_enabled = inst == Inst::Do;
+                    }
+                    None
+                }
+            })
+            .sum();
+        result.into()
+    }
 }
@@ -1,8 +1,9 @@
+use aoc_common::{Answer, Solution};
 use itertools::Itertools;
 
 /// Count the amount of times `kernel` is in `data`, where a `0` in kernel matches everything.
 fn correlate(data: &[&[u8]], data_max_width: usize, kernel: &[&[u8]], kernel_width: usize) -> u32 {
-    assert!(kernel_width > 0 && kernel.len() > 0);
+    assert!(kernel_width > 0 && !kernel.is_empty());
 
     let kernel_height = kernel.len();
     let data_height = data.len();
@@ -13,8 +14,7 @@ fn correlate(data: &[&[u8]], data_max_width: usize, kernel: &[&[u8]], kernel_wid
     for y in 0..y_range {
         'outer: for x in 0..x_range {
             for y_offset in 0..kernel_height {
-                for x_offset in 0..kernel_width {
-                    let k = kernel[y_offset][x_offset];
+                for (x_offset, &k) in kernel[y_offset].iter().enumerate().take(kernel_width) {
                     if k == 0 {
                         continue;
                     }
@@ -44,80 +44,97 @@ fn correlate(data: &[&[u8]], data_max_width: usize, kernel: &[&[u8]], kernel_wid
     count
 }
 
-pub fn day4(data: &str, p1: bool) -> i64 {
-    // P1 kernels
-    let diag_kernel: &[&[u8]] = &[
-        &[b'X', 0, 0, 0],
-        &[0, b'M', 0, 0],
-        &[0, 0, b'A', 0],
-        &[0, 0, 0, b'S'],
-    ];
-    let diag_kernel_m: &[&[u8]] = &[
-        &[0, 0, 0, b'X'],
-        &[0, 0, b'M', 0],
-        &[0, b'A', 0, 0],
-        &[b'S', 0, 0, 0],
-    ];
-    let diag_kernel_r: &[&[u8]] = &[
-        &[b'S', 0, 0, 0],
-        &[0, b'A', 0, 0],
-        &[0, 0, b'M', 0],
-        &[0, 0, 0, b'X'],
-    ];
-    let diag_kernel_mr: &[&[u8]] = &[
-        &[0, 0, 0, b'S'],
-        &[0, 0, b'A', 0],
-        &[0, b'M', 0, 0],
-        &[b'X', 0, 0, 0],
-    ];
-    let vert_kernel: &[&[u8]] = &[&[b'X'], &[b'M'], &[b'A'], &[b'S']];
-    let vert_kernel_r: &[&[u8]] = &[&[b'S'], &[b'A'], &[b'M'], &[b'X']];
-    let horiz_kernel: &[&[u8]] = &[&[b'X', b'M', b'A', b'S']];
-    let horiz_kernel_r: &[&[u8]] = &[&[b'S', b'A', b'M', b'X']];
-
-    // P2 kernels
-    let xmas: &[&[u8]] = &[
-        &[b'M', 0, b'M'],
-        &[0,  b'A', 0],
-        &[b'S', 0, b'S'],
-    ];
-    let xmas_nr: &[&[u8]] = &[
-        &[b'M', 0, b'S'],
-        &[0,  b'A', 0],
-        &[b'M', 0, b'S'],
-    ];
-    let xmas_rn: &[&[u8]] = &[
-        &[b'S', 0, b'M'],
-        &[0,  b'A', 0],
-        &[b'S', 0, b'M'],
-    ];
-    let xmas_rr: &[&[u8]] = &[
-        &[b'S', 0, b'S'],
-        &[0,  b'A', 0],
-        &[b'M', 0, b'M'],
-    ];
+#[derive(Clone)]
+pub struct Day4;
 
+impl Solution for Day4 {
+    type Input = Vec<Vec<u8>>;
 
-    let data = data.lines().map(|l| l.as_bytes()).collect_vec();
-    let max_width = data.iter().map(|l| l.len()).max().unwrap_or(0);
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        Ok(data.lines().map(|l| l.as_bytes().to_vec()).collect_vec())
+    }
 
-    let result = if p1 {
-        correlate(&data, max_width, diag_kernel, 4)
+    fn part1(&self, data: &Self::Input) -> Answer {
+        let diag_kernel: &[&[u8]] = &[
+            &[b'X', 0, 0, 0],
+            &[0, b'M', 0, 0],
+            &[0, 0, b'A', 0],
+            &[0, 0, 0, b'S'],
+        ];
+        let diag_kernel_m: &[&[u8]] = &[
+            &[0, 0, 0, b'X'],
+            &[0, 0, b'M', 0],
+            &[0, b'A', 0, 0],
+            &[b'S', 0, 0, 0],
+        ];
+        let diag_kernel_r: &[&[u8]] = &[
+            &[b'S', 0, 0, 0],
+            &[0, b'A', 0, 0],
+            &[0, 0, b'M', 0],
+            &[0, 0, 0, b'X'],
+        ];
+        let diag_kernel_mr: &[&[u8]] = &[
+            &[0, 0, 0, b'S'],
+            &[0, 0, b'A', 0],
+            &[0, b'M', 0, 0],
+            &[b'X', 0, 0, 0],
+        ];
+        let vert_kernel: &[&[u8]] = &[b"X", b"M", b"A", b"S"];
+        let vert_kernel_r: &[&[u8]] = &[b"S", b"A", b"M", b"X"];
+        let horiz_kernel: &[&[u8]] = &[b"XMAS"];
+        let horiz_kernel_r: &[&[u8]] = &[b"SAMX"];
+
+        let data = data.iter().map(|l| l.as_slice()).collect_vec();
+        let max_width = data.iter().map(|l| l.len()).max().unwrap_or(0);
+
+        let result = correlate(&data, max_width, diag_kernel, 4)
             + correlate(&data, max_width, diag_kernel_m, 4)
             + correlate(&data, max_width, diag_kernel_r, 4)
             + correlate(&data, max_width, diag_kernel_mr, 4)
             + correlate(&data, max_width, vert_kernel, 1)
             + correlate(&data, max_width, vert_kernel_r, 1)
             + correlate(&data, max_width, horiz_kernel, 4)
-            + correlate(&data, max_width, horiz_kernel_r, 4)
-    } else {
-        correlate(&data, max_width, xmas, 3)
+            + correlate(&data, max_width, horiz_kernel_r, 4);
+
+        result.into()
+    }
+
+    fn part2(&self, data: &Self::Input) -> Answer {
+        #[rustfmt::skip]
+        let xmas: &[&[u8]] = &[
+            &[b'M', 0, b'M'],
+            &[0, b'A', 0],
+            &[b'S', 0, b'S'],
+        ];
+        #[rustfmt::skip]
+        let xmas_nr: &[&[u8]] = &[
+            &[b'M', 0, b'S'],
+            &[0, b'A', 0],
+            &[b'M', 0, b'S'],
+        ];
+        #[rustfmt::skip]
+        let xmas_rn: &[&[u8]] = &[
+            &[b'S', 0, b'M'],
+            &[0, b'A', 0],
+            &[b'S', 0, b'M'],
+        ];
+        #[rustfmt::skip]
+        let xmas_rr: &[&[u8]] = &[
+            &[b'S', 0, b'S'],
+            &[0, b'A', 0],
+            &[b'M', 0, b'M'],
+        ];
+
+        let data = data.iter().map(|l| l.as_slice()).collect_vec();
+        let max_width = data.iter().map(|l| l.len()).max().unwrap_or(0);
+
+        let result = correlate(&data, max_width, xmas, 3)
             + correlate(&data, max_width, xmas_nr, 3)
             + correlate(&data, max_width, xmas_rn, 3)
-            + correlate(&data, max_width, xmas_rr, 3)
-    };
+            + correlate(&data, max_width, xmas_rr, 3);
 
-    result as i64
+        result.into()
+    }
 }
 
 #[test]
@@ -1,72 +1,81 @@
 use std::{cmp::Ordering, collections::HashMap};
 
+use anyhow::Context;
+use aoc_common::{parse, Answer, Solution};
 use itertools::Itertools;
 
 #[derive(Clone, Debug)]
-struct Rule {
+pub struct Rule {
     first: u32,
     second: u32,
 }
 
 impl Rule {
-    pub fn parse(line: &str) -> Option<Rule> {
-        let (a, b) = line.split_once('|')?;
+    pub fn parse(data: &str, line: &str) -> anyhow::Result<Rule> {
+        let (a, b) = line
+            .split_once('|')
+            .ok_or_else(|| parse::error(data, line, "expected a rule `<page>|<page>`"))?;
 
-        let first = a.trim().parse::<u32>().ok()?;
-        let second = b.trim().parse::<u32>().ok()?;
-        Some(Rule { first, second })
+        let first = parse::from_str::<u32>(data, a.trim())?;
+        let second = parse::from_str::<u32>(data, b.trim())?;
+        Ok(Rule { first, second })
     }
 }
 
-pub fn day5(data: &str, p1: bool) -> i64 {
-    let lines = data.lines().collect_vec();
-    let (empty_line_idx, _) = lines
+fn is_correct_order(pages: &[u32], rules: &[Rule]) -> bool {
+    rules
         .iter()
-        .find_position(|a| a.is_empty())
-        .expect("an empty line");
-
-    let rules = &lines[..empty_line_idx];
-    let rules = rules
-        .into_iter()
-        .filter_map(|l| Rule::parse(l))
-        .collect_vec();
-    let pages = &lines[empty_line_idx + 1..];
-    let pages = pages
-        .into_iter()
-        .filter_map(|l| {
-            let ns = l
-                .split(',')
-                .filter_map(|n| n.parse::<u32>().ok())
-                .collect_vec();
-            if ns.is_empty() {
-                None
-            } else {
-                Some(ns)
-            }
+        .filter_map(|r| {
+            let first_pos = pages.iter().position(|p| *p == r.first)?;
+            let second_pos = pages.iter().position(|p| *p == r.second)?;
+            Some(first_pos < second_pos)
         })
-        .collect_vec();
+        .all(|v| v)
+}
+
+#[derive(Clone)]
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Vec<Rule>, Vec<Vec<u32>>);
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let lines = data.lines().collect_vec();
+        let (empty_line_idx, _) = lines
+            .iter()
+            .find_position(|a| a.is_empty())
+            .context("expected an empty line between the rules and the updates")?;
 
-    fn is_correct_order(pages: &[u32], rules: &[Rule]) -> bool {
-        rules
+        let rules = &lines[..empty_line_idx];
+        let rules = rules.iter().map(|l| Rule::parse(data, l)).try_collect()?;
+        let pages = &lines[empty_line_idx + 1..];
+        let pages = pages
             .iter()
-            .filter_map(|r| {
-                let first_pos = pages.iter().position(|p| *p == r.first)?;
-                let second_pos = pages.iter().position(|p| *p == r.second)?;
-                Some(first_pos < second_pos)
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                l.split(',')
+                    .map(|n| parse::from_str::<u32>(data, n.trim()))
+                    .try_collect()
             })
-            .all(|v| v == true)
+            .try_collect()?;
+
+        Ok((rules, pages))
     }
 
-    let result: u32 = if p1 {
-        pages
+    fn part1(&self, (rules, pages): &Self::Input) -> Answer {
+        let result: u32 = pages
             .iter()
-            .filter(|p| is_correct_order(p, &rules))
+            .filter(|p| is_correct_order(p, rules))
             .map(|p| {
                 let middle_idx = (p.len() - 1) / 2;
                 p[middle_idx]
             })
-            .sum()
-    } else {
+            .sum();
+
+        result.into()
+    }
+
+    fn part2(&self, (rules, pages): &Self::Input) -> Answer {
         // A HashMap that has for a key, its list of page numbers that are all ordered after the
         // key.
         let mut is_smaller_map = HashMap::<u32, Vec<u32>>::new();
@@ -79,7 +88,7 @@ pub fn day5(data: &str, p1: bool) -> i64 {
 
         let mut disordered_pages = pages
             .iter()
-            .filter(|p| !is_correct_order(p, &rules))
+            .filter(|p| !is_correct_order(p, rules))
             .cloned()
             .collect_vec();
         for pages in disordered_pages.iter_mut() {
@@ -100,17 +109,17 @@ pub fn day5(data: &str, p1: bool) -> i64 {
                 Ordering::Equal
             });
 
-            assert!(is_correct_order(pages, &rules));
+            assert!(is_correct_order(pages, rules));
         }
 
-        disordered_pages
+        let result: u32 = disordered_pages
             .into_iter()
             .map(|p| {
                 let middle_idx = (p.len() - 1) / 2;
                 p[middle_idx]
             })
-            .sum()
-    };
+            .sum();
 
-    result as i64
+        result.into()
+    }
 }
@@ -1,90 +1,47 @@
-use enumflags2::{bitflags, BitFlags};
-use itertools::Itertools;
-
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+use aoc_common::{
+    dir::{Dir4, DirSet},
+    grid::Grid,
+    vec2::Vec2,
+    Answer, Solution,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 enum Loc {
-    Nothing { visited: bool, dir: BitFlags<Dir> },
+    Nothing { visited: bool, dir: DirSet },
     Obstacle,
     Pos,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-#[bitflags]
-#[repr(u8)]
-enum Dir {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl Dir {
-    fn step(&self, x: i32, y: i32) -> (i32, i32) {
-        match *self {
-            Self::Up => (x, y - 1),
-            Self::Right => (x + 1, y),
-            Self::Down => (x, y + 1),
-            Self::Left => (x - 1, y),
-        }
-    }
-
-    fn turn(&mut self) {
-        *self = match *self {
-            Self::Up => Self::Right,
-            Self::Right => Self::Down,
-            Self::Down => Self::Left,
-            Self::Left => Self::Up,
-        };
-    }
-}
-
-/// Whether the `x`, `y` position is in the map bounds.
-fn is_in_bounds(x: i32, y: i32, width: usize, height: usize) -> bool {
-    x >= 0 && y >= 0 && (x as isize) < (width as isize) && (y as isize) < (height as isize)
-}
-
 #[derive(Clone)]
-struct Map {
-    pub map: Vec<Loc>,
-    width: usize,
-    height: usize,
-    x: i32,
-    y: i32,
-    dir: Dir,
+pub struct Map {
+    map: Grid<Loc>,
+    pos: Vec2,
+    dir: Dir4,
 }
 
 impl Map {
-    fn new(map: Vec<Loc>, x: i32, y: i32, width: usize, height: usize) -> Self {
+    fn new(map: Grid<Loc>, pos: Vec2) -> Self {
         Self {
             map,
-            width,
-            height,
-            x,
-            y,
-            dir: Dir::Up,
+            pos,
+            dir: Dir4::Up,
         }
     }
 
     /// Get the next position and direction of the agent.
     /// The agent may turn multiple times but only step once.
-    fn next_pos(&self) -> Result<(i32, i32, Dir), bool> {
-        let mut x = self.x;
-        let mut y = self.y;
+    fn next_pos(&self) -> Result<(Vec2, Dir4), bool> {
         let mut dir = self.dir;
         for _ in 0..4 {
-            let (x_temp, y_temp) = dir.step(x, y);
-            if !is_in_bounds(x_temp, y_temp, self.width, self.height) {
+            let pos = self.pos + dir.to_vec2();
+            let Some(loc) = self.map.get(pos) else {
                 return Err(false);
-            }
+            };
 
-            let loc = self.map[(x_temp + y_temp * self.width as i32) as usize];
             if !matches!(loc, Loc::Nothing { .. }) {
-                dir.turn();
+                dir = dir.rotate(1);
             } else {
-                x = x_temp;
-                y = y_temp;
-
-                return Ok((x, y, dir));
+                return Ok((pos, dir));
             }
         }
         // Detected a cycle by turning four times in a row.
@@ -96,9 +53,7 @@ impl Map {
     /// Return `Some(true)` if a cycle was detected, `Some(false)` if the agent went out of bounds.
     /// Otherwise `None`, the path did not terminate yet.
     fn step(&mut self) -> Option<bool> {
-        let idx = (self.x + self.y * self.width as i32) as usize;
-
-        match &mut self.map[idx] {
+        match &mut self.map[self.pos] {
             Loc::Nothing {
                 visited: true,
                 dir: d,
@@ -107,17 +62,17 @@ impl Map {
             }
             Loc::Nothing { visited, dir } => {
                 *visited = true;
-                *dir |= self.dir;
+                dir.insert(self.dir);
             }
             val => {
                 *val = Loc::Nothing {
                     visited: true,
-                    dir: self.dir.into(),
+                    dir: DirSet::from([self.dir]),
                 }
             }
         };
 
-        (self.x, self.y, self.dir) = match self.next_pos() {
+        (self.pos, self.dir) = match self.next_pos() {
             // Detected termination condition.
             Err(val) => return Some(val),
             // Stepped successfully.
@@ -139,23 +94,29 @@ impl Map {
         }
     }
 
-    fn print(&self, start_idx: usize) {
-        println!("filled out map:");
-        for l in &self.map.iter().enumerate().chunks(self.width) {
-            let line = l
-                .map(|(i, loc)| {
-                    if i == start_idx {
+    fn print(&self, start: Vec2) {
+        if !log::log_enabled!(log::Level::Debug) {
+            return;
+        }
+
+        log::debug!("filled out map:");
+        for (y, row) in self.map.rows().enumerate() {
+            let line = row
+                .iter()
+                .enumerate()
+                .map(|(x, loc)| {
+                    if Vec2(x as i32, y as i32) == start {
                         return '^';
                     }
                     match loc {
                         Loc::Nothing { visited: false, .. } => '.',
                         Loc::Nothing { visited: true, dir }
-                            if (*dir & (Dir::Up | Dir::Down)).is_empty() =>
+                            if (*dir & DirSet::from([Dir4::Up, Dir4::Down])).is_empty() =>
                         {
                             '-'
                         }
                         Loc::Nothing { visited: true, dir }
-                            if (*dir & (Dir::Left | Dir::Right)).is_empty() =>
+                            if (*dir & DirSet::from([Dir4::Left, Dir4::Right])).is_empty() =>
                         {
                             '|'
                         }
@@ -164,70 +125,56 @@ impl Map {
                         Loc::Obstacle => '#',
                     }
                 })
-                .join("");
+                .collect::<String>();
 
-            println!("{line}");
+            log::debug!("{line}");
         }
     }
 }
 
-pub fn day6(data: &str, p1: bool) -> i64 {
-    let map = data
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            l.chars()
-                .map(|c| match c {
-                    '.' => Loc::Nothing {
-                        visited: false,
-                        dir: BitFlags::empty(),
-                    },
-                    '#' => Loc::Obstacle,
-                    '^' => Loc::Pos,
-                    c => panic!("unexpected char '{c}'"),
-                })
-                .collect_vec()
-        })
-        .collect_vec();
-
-    let width = map.get(0).expect("map").len();
-    let height = map.len();
+#[derive(Clone)]
+pub struct Day6;
 
-    assert!(map.iter().all(|l| l.len() == width));
-    let map = map
-        .into_iter()
-        .map(|l| l.into_iter())
-        .flatten()
-        .collect_vec();
+impl Solution for Day6 {
+    type Input = Map;
 
-    let pos_idx = map
-        .iter()
-        .position(|v| *v == Loc::Pos)
-        .expect("position in map");
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let (map, [pos]) = Grid::parse(data, ['^'], |c| match c {
+            '.' => Some(Loc::Nothing {
+                visited: false,
+                dir: DirSet::new(),
+            }),
+            '#' => Some(Loc::Obstacle),
+            '^' => Some(Loc::Pos),
+            _ => None,
+        })?;
 
-    let pos_x = pos_idx % width;
-    let pos_y = pos_idx / width;
+        log::info!("pos at [{}, {}]", pos.x(), pos.y());
 
-    println!("pos at [{pos_x}, {pos_y}]");
+        Ok(Map::new(map, pos))
+    }
 
-    let result = if p1 {
-        let mut map = Map::new(map, pos_x as i32, pos_y as i32, width, height);
+    fn part1(&self, initial_map: &Self::Input) -> Answer {
+        let mut map = initial_map.clone();
         map.fill();
         let result: usize = map
             .map
+            .cells()
             .iter()
             .filter(|loc| matches!(*loc, Loc::Nothing { visited: true, .. }))
             .count();
-        map.print(pos_idx);
+        map.print(initial_map.pos);
+
+        result.into()
+    }
 
-        result
-    } else {
-        let initial_map = Map::new(map, pos_x as i32, pos_y as i32, width, height);
+    fn part2(&self, initial_map: &Self::Input) -> Answer {
+        let start = initial_map.pos;
         let mut map = initial_map.clone();
 
         let mut obstruction_positions = Vec::new();
         loop {
-            let (x, y, _) = match map.next_pos() {
+            let (obstruction_pos, _) = match map.next_pos() {
                 Err(true) => unimplemented!("turning-cycles are not implemented"),
                 Err(false) => {
                     // Agent went outside of bounds, we are finished.
@@ -236,18 +183,16 @@ pub fn day6(data: &str, p1: bool) -> i64 {
                 Ok(val) => val,
             };
 
-            let obstruction_idx = (x + y * width as i32) as usize;
-
-            if !obstruction_positions.contains(&obstruction_idx) && obstruction_idx != pos_idx {
+            if !obstruction_positions.contains(&obstruction_pos) && obstruction_pos != start {
                 let mut temp_map = initial_map.clone();
 
-                temp_map.map[obstruction_idx] = Loc::Obstacle;
+                temp_map.map[obstruction_pos] = Loc::Obstacle;
                 let is_cycle = temp_map.fill();
 
                 if is_cycle {
-                    // temp_map.print(pos_idx);
+                    // temp_map.print(start);
 
-                    obstruction_positions.push(obstruction_idx);
+                    obstruction_positions.push(obstruction_pos);
                 }
             }
 
@@ -256,8 +201,6 @@ pub fn day6(data: &str, p1: bool) -> i64 {
             }
         }
 
-        obstruction_positions.len()
-    };
-
-    result as i64
+        obstruction_positions.len().into()
+    }
 }
@@ -1,3 +1,6 @@
+use std::ops::Range;
+
+use aoc_common::{parse, Answer, Solution};
 use itertools::Itertools;
 
 #[derive(Clone, Debug)]
@@ -8,6 +11,10 @@ struct Equation<'n> {
 
 impl Equation<'_> {
     fn print_eq(&self, ops: impl IntoIterator<Item = Op>, ans: Option<i64>) {
+        if !log::log_enabled!(log::Level::Debug) {
+            return;
+        }
+
         let eq = ops
             .into_iter()
             .zip(&self.nums[0..self.nums.len() - 1])
@@ -16,9 +23,9 @@ impl Equation<'_> {
             .join("");
 
         if let Some(ans) = ans {
-            println!("{eq} = {ans}{}", if ans == self.ans { " ✓" } else { "" });
+            log::debug!("{eq} = {ans}{}", if ans == self.ans { " ✓" } else { "" });
         } else {
-            println!("{}: {eq}", self.ans);
+            log::debug!("{}: {eq}", self.ans);
         }
     }
 
@@ -84,57 +91,77 @@ impl std::fmt::Display for Op {
     }
 }
 
-pub fn day7(data: &str, p1: bool) -> i64 {
-    let (ans, nums): (Vec<(i64, usize)>, Vec<Vec<i64>>) = data
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            let (ans, nums) = l.split_once(':').expect("equation on each line");
-
-            let ans = ans.trim().parse::<i64>().expect("answer must be a number");
-            let nums = nums
-                .trim()
-                .split(' ')
-                .map(|n| n.parse::<i64>().expect("numbers"))
-                .collect_vec();
-
-            ((ans, nums.len()), nums)
+/// All equations, with the numbers of every equation stored in one contiguous list.
+pub struct Equations {
+    nums: Vec<i64>,
+    eqs: Vec<(i64, Range<usize>)>,
+}
+
+impl Equations {
+    fn iter(&self) -> impl Iterator<Item = Equation<'_>> {
+        self.eqs.iter().map(|(ans, range)| Equation {
+            ans: *ans,
+            nums: &self.nums[range.clone()],
         })
-        .unzip();
-
-    let nums = nums
-        .into_iter()
-        .map(|n| n.into_iter())
-        .flatten()
-        .collect_vec();
-    let eqs = ans
-        .into_iter()
-        .scan(0, |sum, (ans, len)| {
-            let idx = *sum;
-            *sum += len;
-
-            let range = idx..*sum;
-            Some(Equation {
-                ans,
-                nums: &nums[range],
+    }
+
+    /// Sum the answers of all equations that are possible with the set of operators `ops`.
+    fn calibration_result(&self, ops: &[Op]) -> i64 {
+        self.iter()
+            .filter_map(|eq| {
+                let used_ops = eq.is_possible(ops)?;
+                eq.print_eq(used_ops, Some(eq.ans));
+                Some(eq.ans)
             })
-        })
-        .collect_vec();
-
-    let operators: &[Op] = if p1 {
-        &[Op::Add, Op::Multiply]
-    } else {
-        &[Op::Add, Op::Multiply, Op::Concat]
-    };
-
-    let result: i64 = eqs
-        .into_iter()
-        .filter_map(|eq| {
-            let ops = eq.is_possible(operators)?;
-            eq.print_eq(ops, Some(eq.ans));
-            Some(eq.ans)
-        })
-        .sum();
+            .sum()
+    }
+}
 
-    result as i64
+#[derive(Clone)]
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Equations;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let (ans, nums): (Vec<(i64, usize)>, Vec<Vec<i64>>) = data
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                let (ans, nums) = l
+                    .split_once(':')
+                    .ok_or_else(|| parse::error(data, l, "expected `<answer>: <numbers>`"))?;
+
+                let ans = parse::from_str::<i64>(data, ans.trim())?;
+                let nums = nums
+                    .trim()
+                    .split(' ')
+                    .map(|n| parse::from_str::<i64>(data, n))
+                    .try_collect::<_, Vec<_>, _>()?;
+
+                anyhow::Ok(((ans, nums.len()), nums))
+            })
+            .process_results(|eqs| eqs.unzip())?;
+
+        let nums = nums.into_iter().flatten().collect_vec();
+        let eqs = ans
+            .into_iter()
+            .scan(0, |sum, (ans, len)| {
+                let idx = *sum;
+                *sum += len;
+                Some((ans, idx..*sum))
+            })
+            .collect_vec();
+
+        Ok(Equations { nums, eqs })
+    }
+
+    fn part1(&self, eqs: &Self::Input) -> Answer {
+        eqs.calibration_result(&[Op::Add, Op::Multiply]).into()
+    }
+
+    fn part2(&self, eqs: &Self::Input) -> Answer {
+        eqs.calibration_result(&[Op::Add, Op::Multiply, Op::Concat])
+            .into()
+    }
 }
@@ -1,5 +1,6 @@
 use std::hash::{DefaultHasher, Hash, Hasher};
 
+use aoc_common::{parse, Answer, Solution};
 use itertools::Itertools;
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -37,13 +38,17 @@ impl MultiBlock {
     }
 }
 
-struct File {
+pub struct File {
     id: u32,
     file_blocks: u32,
     free_blocks: u32,
 }
 
 fn print_disk_layout(blocks: impl IntoIterator<Item = Block>) {
+    if !log::log_enabled!(log::Level::Debug) {
+        return;
+    }
+
     let layout = blocks
         .into_iter()
         .map(|b| match b {
@@ -55,20 +60,19 @@ fn print_disk_layout(blocks: impl IntoIterator<Item = Block>) {
             Block::Free => '.',
         })
         .join("");
-    println!("{layout}");
+    log::debug!("{layout}");
 }
 
 const MAX_PRINT_SIZE: usize = 50;
 
 fn compact_blocks(files: &[File]) -> Vec<Block> {
     let mut blocks = files
-        .into_iter()
-        .map(|f| {
+        .iter()
+        .flat_map(|f| {
             (0..f.file_blocks)
                 .map(move |_| Block::File(f.id))
                 .chain((0..f.free_blocks).map(|_| Block::Free))
         })
-        .flatten()
         .collect_vec();
 
     let disk_size = blocks.len();
@@ -115,8 +119,8 @@ fn compact_blocks(files: &[File]) -> Vec<Block> {
 
 fn compact_muti_blocks(files: &[File]) -> Vec<Block> {
     let mut blocks = files
-        .into_iter()
-        .map(|f| {
+        .iter()
+        .flat_map(|f| {
             [
                 MultiBlock::File {
                     id: f.id,
@@ -127,7 +131,6 @@ fn compact_muti_blocks(files: &[File]) -> Vec<Block> {
                 },
             ]
         })
-        .flatten()
         .collect_vec();
 
     let disk_size: usize = files
@@ -142,10 +145,7 @@ fn compact_muti_blocks(files: &[File]) -> Vec<Block> {
     // If the file doesn't fill the whole free space, fill the rest with free blocks.
     // Do one pass for all files.
     let mut idx_a_iter = (0..blocks.len()).rev();
-    loop {
-        let Some(idx_a) = idx_a_iter.next() else {
-            break;
-        };
+    while let Some(idx_a) = idx_a_iter.next() {
         let MultiBlock::File { n_blocks, .. } = blocks[idx_a] else {
             continue;
         };
@@ -184,34 +184,8 @@ fn compact_muti_blocks(files: &[File]) -> Vec<Block> {
     blocks.iter().flat_map(MultiBlock::blocks).collect_vec()
 }
 
-pub fn day9(data: &str, p1: bool) -> i64 {
-    let mut chars = data.trim().chars().collect_vec();
-    if chars.len() % 2 != 0 {
-        chars.push('0');
-    }
-
-    let files = chars
-        .into_iter()
-        .tuples::<(_, _)>()
-        .enumerate()
-        .map(|(id, (file_blocks, free_blocks))| {
-            let file_blocks: u32 = file_blocks.to_digit(10).expect("digit");
-            let free_blocks: u32 = free_blocks.to_digit(10).expect("digit");
-
-            File {
-                id: id as u32,
-                file_blocks,
-                free_blocks,
-            }
-        })
-        .collect_vec();
-
-    let blocks = if p1 {
-        compact_blocks(&files)
-    } else {
-        compact_muti_blocks(&files)
-    };
-
+/// The checksum of the compacted disk `blocks`.
+fn checksum(blocks: &[Block]) -> i64 {
     let result: u64 = blocks
         .iter()
         .enumerate()
@@ -226,3 +200,39 @@ pub fn day9(data: &str, p1: bool) -> i64 {
 
     result as i64
 }
+
+#[derive(Clone)]
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<File>;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let digits = parse::chars(data.trim())
+            .map(|(c, at)| {
+                c.to_digit(10)
+                    .ok_or_else(|| parse::error(data, at, format!("expected a digit, got '{c}'")))
+            })
+            .try_collect::<_, Vec<_>, _>()?;
+
+        Ok(digits
+            .into_iter()
+            .chain([0])
+            .tuples::<(_, _)>()
+            .enumerate()
+            .map(|(id, (file_blocks, free_blocks))| File {
+                id: id as u32,
+                file_blocks,
+                free_blocks,
+            })
+            .collect_vec())
+    }
+
+    fn part1(&self, files: &Self::Input) -> Answer {
+        checksum(&compact_blocks(files)).into()
+    }
+
+    fn part2(&self, files: &Self::Input) -> Answer {
+        checksum(&compact_muti_blocks(files)).into()
+    }
+}
@@ -0,0 +1,28 @@
+use aoc2024::day18::{
+    shortest_path::{Loc, ShortestPath, Vec2},
+    Day18,
+};
+use aoc_common::{grid::Grid, Answer, Solution};
+
+const EXAMPLE: &str = "5,4\n4,2\n4,5\n3,0\n2,1\n6,3\n2,4\n1,5\n0,6\n3,3\n2,6\n5,1\n1,2\n5,5\n2,5\n\
+                       6,5\n1,4\n0,4\n6,4\n1,1\n6,1\n1,0\n0,5\n1,6\n2,0\n";
+
+#[test]
+fn example() {
+    let day = Day18 { size: 7, bytes: 12 };
+    let input = day.parse(EXAMPLE).unwrap();
+    assert_eq!(day.part1(&input), Answer::Int(22));
+    assert_eq!(day.part2(&input), Answer::Coord(6, 1));
+}
+
+#[test]
+fn shortest_path_around_a_wall() {
+    // 3x3 with a wall in the middle row, open on the right.
+    let mut map = Grid::new(3, 3, Loc::<()>::Empty);
+    map[Vec2(0, 1)] = Loc::Obstacle;
+    map[Vec2(1, 1)] = Loc::Obstacle;
+
+    let mut sp = ShortestPath::new(Vec2(0, 0), 3, 3);
+    sp.calc(&map);
+    assert_eq!(sp.steps_to(Vec2(0, 2)), Some(6));
+}
@@ -1,3 +1,4 @@
+use aoc_common::{Answer, Solution, parse};
 use itertools::Itertools;
 
 fn largest_numerical_subsequence(nums: &[u8], len: usize) -> (usize, Vec<usize>) {
@@ -41,24 +42,32 @@ fn largest_numerical_subsequence(nums: &[u8], len: usize) -> (usize, Vec<usize>)
     (result, result_indices)
 }
 
-pub fn run(data: &str, p1: bool) -> impl std::fmt::Display {
-    let banks = data
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            let nums = l
-                .as_bytes()
-                .iter()
-                .map(|b| (b - b'0') as u8)
-                .collect::<Vec<_>>();
-            nums
-        })
-        .collect::<Vec<_>>();
-
-    if p1 {
-        return banks
-            .into_iter()
-            .map(move |bank| {
+#[derive(Clone)]
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Vec<u8>>;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        data.lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                parse::chars(l)
+                    .map(|(c, at)| {
+                        let digit = c
+                            .to_digit(10)
+                            .ok_or_else(|| parse::error(data, at, "expected a digit"))?;
+                        Ok(digit as u8)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn part1(&self, banks: &Self::Input) -> Answer {
+        banks
+            .iter()
+            .map(|bank| {
                 let mut largest = bank
                     .iter()
                     .copied()
@@ -67,7 +76,7 @@ pub fn run(data: &str, p1: bool) -> impl std::fmt::Display {
 
                 let mut n0 = largest.next().expect("must not be empty");
                 let Some(mut n1) = largest.next() else {
-                    return (n0.1 as usize, (n0.0, n0.0), bank);
+                    return (n0.1 as usize, (n0.0, n0.0), bank.as_slice());
                 };
 
                 // Sort in order of the bank.
@@ -78,8 +87,8 @@ pub fn run(data: &str, p1: bool) -> impl std::fmt::Display {
                 fn make_n(
                     n0: (usize, u8), // (idx of the number in the bank, number)
                     n1: (usize, u8), // (idx of the number in the bank, number)
-                    bank: Vec<u8>,
-                ) -> (usize, (usize, usize), Vec<u8>) {
+                    bank: &[u8],
+                ) -> (usize, (usize, usize), &[u8]) {
                     ((n0.1 as usize) * 10 + (n1.1 as usize), (n0.0, n1.0), bank)
                 }
 
@@ -100,43 +109,51 @@ pub fn run(data: &str, p1: bool) -> impl std::fmt::Display {
             })
             .map(|(n, (i0, i1), bank)| {
                 // Pretty print.
-                let s = bank
-                    .iter()
-                    .enumerate()
-                    .map(|(idx, n)| {
-                        if idx == i0 || idx == i1 {
-                            format!("[{n}]")
-                        } else {
-                            format!("{n}")
-                        }
-                    })
-                    .join("");
-                println!("{s} -> {n}");
+                if log::log_enabled!(log::Level::Debug) {
+                    let s = bank
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, n)| {
+                            if idx == i0 || idx == i1 {
+                                format!("[{n}]")
+                            } else {
+                                format!("{n}")
+                            }
+                        })
+                        .join("");
+                    log::debug!("{s} -> {n}");
+                }
 
                 n
             })
-            .sum::<usize>();
+            .sum::<usize>()
+            .into()
     }
 
-    banks
-        .iter()
-        .map(|bank| (largest_numerical_subsequence(bank, 12), bank))
-        .map(|((n, idx), bank)| {
-            // Pretty print.
-            let s = bank
-                .iter()
-                .enumerate()
-                .map(|(i, n)| {
-                    if idx.iter().any(|j| i == *j) {
-                        format!("[{n}]")
-                    } else {
-                        format!("{n}")
-                    }
-                })
-                .join("");
-            println!("{s} -> {n}");
-
-            n
-        })
-        .sum::<usize>()
+    fn part2(&self, banks: &Self::Input) -> Answer {
+        banks
+            .iter()
+            .map(|bank| (largest_numerical_subsequence(bank, 12), bank))
+            .map(|((n, idx), bank)| {
+                // Pretty print.
+                if log::log_enabled!(log::Level::Debug) {
+                    let s = bank
+                        .iter()
+                        .enumerate()
+                        .map(|(i, n)| {
+                            if idx.contains(&i) {
+                                format!("[{n}]")
+                            } else {
+                                format!("{n}")
+                            }
+                        })
+                        .join("");
+                    log::debug!("{s} -> {n}");
+                }
+
+                n
+            })
+            .sum::<usize>()
+            .into()
+    }
 }
@@ -1,48 +1,53 @@
 use std::ops::RangeInclusive;
 
-use itertools::Itertools;
+use aoc_common::{Answer, Solution, parse};
+use nom::{character::complete::char, combinator::map, error::context, sequence::separated_pair};
 
-pub fn run(data: &str, p1: bool) -> impl std::fmt::Display {
-    let mut lines = data.lines();
-    let mut ranges_overlapping = (&mut lines)
-        .take_while(|l| !l.is_empty())
-        .map(|l| {
-            let (a, b) = l.split_once('-').expect("range does not contain `-`");
-            let a = a.parse::<usize>().unwrap();
-            let b = b.parse::<usize>().unwrap();
-            a..=b
-        })
-        .collect_vec();
+#[derive(Clone)]
+pub struct Day5;
 
-    // Sort ranges by start position to simplify merging.
-    ranges_overlapping.sort_by_key(|r| *r.start());
+impl Solution for Day5 {
+    /// The disjoint fresh ingredient ID ranges and the available ingredient IDs.
+    type Input = (Vec<RangeInclusive<usize>>, Vec<usize>);
 
-    // Merge overlapping ranges into a disjoint set.
-    let mut ranges = Vec::<RangeInclusive<usize>>::new();
-    for r in ranges_overlapping {
-        if let Some(last) = ranges.last_mut() {
-            // Since ranges are sorted by start, we only need to check if the current
-            // range starts before the last one ends to determine overlap.
-            if *r.start() <= *last.end() {
-                let new_end = (*last.end()).max(*r.end());
-                *last = *last.start()..=new_end;
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let range = separated_pair(parse::uint::<usize>, char('-'), parse::uint::<usize>);
+        let range = context("range", map(range, |(a, b)| a..=b));
+        let (mut ranges_overlapping, ids) = parse::all(
+            data,
+            separated_pair(
+                parse::lines(range),
+                parse::section_break,
+                parse::lines(parse::uint),
+            ),
+        )?;
+
+        // Sort ranges by start position to simplify merging.
+        ranges_overlapping.sort_by_key(|r| *r.start());
+
+        // Merge overlapping ranges into a disjoint set.
+        let mut ranges = Vec::<RangeInclusive<usize>>::new();
+        for r in ranges_overlapping {
+            if let Some(last) = ranges.last_mut() {
+                // Since ranges are sorted by start, we only need to check if the current
+                // range starts before the last one ends to determine overlap.
+                if *r.start() <= *last.end() {
+                    let new_end = (*last.end()).max(*r.end());
+                    *last = *last.start()..=new_end;
+                } else {
+                    ranges.push(r);
+                }
             } else {
                 ranges.push(r);
             }
-        } else {
-            ranges.push(r);
         }
-    }
 
-    let ids = lines
-        .filter(|l| !l.is_empty())
-        .map(|l| l.parse::<usize>().unwrap())
-        .collect_vec();
+        Ok((ranges, ids))
+    }
 
-    if p1 {
-        return ids
-            .into_iter()
-            .filter(|id| {
+    fn part1(&self, (ranges, ids): &Self::Input) -> Answer {
+        ids.iter()
+            .filter(|&id| {
                 // Use binary search to find if the id is contained in any range.
                 ranges
                     .binary_search_by(|r| {
@@ -56,10 +61,17 @@ pub fn run(data: &str, p1: bool) -> impl std::fmt::Display {
                     })
                     .is_ok()
             })
-            .count();
+            .count()
+            .into()
     }
 
-    // Since the `ranges` are already disjoint, all we have to do is sum up the ranges.
-    // Note the ranges are inclusive hence the plus one.
-    ranges.into_iter().map(|r| *r.end() - *r.start() + 1).sum()
+    fn part2(&self, (ranges, _): &Self::Input) -> Answer {
+        // Since the `ranges` are already disjoint, all we have to do is sum up the ranges.
+        // Note the ranges are inclusive hence the plus one.
+        ranges
+            .iter()
+            .map(|r| *r.end() - *r.start() + 1)
+            .sum::<usize>()
+            .into()
+    }
 }
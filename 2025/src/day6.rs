@@ -1,17 +1,21 @@
+use anyhow::Context;
+use aoc_common::{Answer, Solution, parse};
 use itertools::Itertools;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Op {
+pub enum Op {
     Add,
     Mul,
 }
 
-impl From<char> for Op {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Op {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '+' => Self::Add,
-            '*' => Self::Mul,
-            op => panic!("invalid op '{op}'"),
+            '+' => Ok(Self::Add),
+            '*' => Ok(Self::Mul),
+            op => Err(format!("invalid op '{op}'")),
         }
     }
 }
@@ -35,36 +39,59 @@ impl Op {
     fn apply(self, nums: &[i64]) -> i64 {
         match self {
             Self::Add => nums.iter().sum(),
-            Self::Mul => nums.iter().copied().fold(1, |acc, v| acc * v),
+            Self::Mul => nums.iter().product(),
         }
     }
 }
 
-pub fn run(data: &str, p1: bool) -> impl std::fmt::Display {
-    let lines = data
-        .lines()
-        .map(str::trim_end)
-        .filter(|l| !l.is_empty())
-        .collect_vec();
-
-    // Get operators from the last line.
-    let ops = lines
-        .last()
-        .unwrap()
-        .chars()
-        .enumerate()
-        .filter(|(_, c)| !c.is_whitespace())
-        .map(|(i, c)| (i, Op::from(c)))
-        .collect_vec();
-
-    if p1 {
+#[derive(Clone)]
+pub struct Day6;
+
+impl Solution for Day6 {
+    /// The lines of the worksheet and the operators with their column index.
+    type Input = (Vec<String>, Vec<(usize, Op)>);
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        let lines = data
+            .lines()
+            .map(str::trim_end)
+            .filter(|l| !l.is_empty())
+            .collect_vec();
+
+        // Get operators from the last line.
+        let (last, rows) = lines.split_last().context("the input is empty")?;
+        let ops = parse::chars(last)
+            .enumerate()
+            .filter(|(_, (c, _))| !c.is_whitespace())
+            .map(|(i, (c, at))| {
+                let op = Op::try_from(c).map_err(|e| parse::error(data, at, e))?;
+                Ok((i, op))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        // The parts read the numbers both by rows and by digit columns.
+        for row in rows {
+            let not_digit = parse::chars(row).find(|(c, _)| !c.is_ascii_digit() && *c != ' ');
+            if let Some((c, at)) = not_digit {
+                return Err(parse::error(data, at, format!("'{c}' is not a digit")));
+            }
+            for num in row.split_whitespace() {
+                parse::from_str::<i64>(data, num)?;
+            }
+        }
+
+        let lines = lines.into_iter().map(str::to_owned).collect_vec();
+        Ok((lines, ops))
+    }
+
+    fn part1(&self, (lines, ops): &Self::Input) -> Answer {
         let mut columns = Vec::new();
         // Each column of numbers in data is a problem, so transpose.
         for (i, n) in lines[..lines.len() - 1].iter().flat_map(|line| {
             line.split_whitespace()
                 .map(str::trim)
                 .filter(|v| !v.is_empty())
-                .map(|v| v.parse::<i64>().unwrap())
+                .map(|v| v.parse::<i64>().expect("validated by the parse"))
                 .enumerate()
         }) {
             let v = match columns.get_mut(i) {
@@ -78,75 +105,77 @@ pub fn run(data: &str, p1: bool) -> impl std::fmt::Display {
         }
 
         let mut total = 0_i64;
-        for (col, (_, op)) in columns.into_iter().zip(ops) {
+        for (col, (_, op)) in columns.into_iter().zip(ops.iter().copied()) {
             let res = op.apply(&col);
-            println!("{} = {res}", op.format_numbers(&col));
+            log::debug!("{} = {res}", op.format_numbers(&col));
             total += res;
         }
-        return total;
+        total.into()
     }
 
-    let mut op_indices = ops.iter().map(|(i, _)| *i).collect_vec();
-    op_indices.push(lines.iter().map(|l| l.len()).max().unwrap());
-    let op_ranges = op_indices
-        .iter()
-        .copied()
-        .tuple_windows::<(_, _)>()
-        .collect_vec();
-
-    let mut columns = Vec::new();
-
-    // In addition to the problem columns, each digit is also a column,
-    // where the digit in the first row is the most significant digit
-    // and the digit in the last (non-empty) row is the least significant digit
-    // of a single number.
-    // The amount of numbers in a problem is determined by the maximum amount of (non-empty) digit
-    // columns.
-    for (i, n) in lines[..lines.len() - 1].iter().flat_map(|line| {
-        // Slice each number of the current row by the ranges determined by the operators,
-        // this is possible since all numbers are aligned (and so are the operators).
-        op_ranges
+    fn part2(&self, (lines, ops): &Self::Input) -> Answer {
+        let mut op_indices = ops.iter().map(|(i, _)| *i).collect_vec();
+        op_indices.push(lines.iter().map(|l| l.len()).max().unwrap());
+        let op_ranges = op_indices
             .iter()
             .copied()
-            .enumerate() // enumerate columns
-            .map(|(i, (a, b))| (i, &line[a..b.min(line.len())]))
-    }) {
-        // Get the right column vector.
-        let v = match columns.get_mut(i) {
-            None => {
-                columns.push(Vec::new());
-                columns.last_mut().unwrap()
-            }
-            Some(v) => v,
-        };
-
-        // Each digit `j` of the current number `i` within a column in the data with some row index
-        // `r` is actually the `r`-th digit of the `j`-th number within that column i.
-        for (j, digit) in n.bytes().enumerate() {
-            let c = char::from(digit);
-            if c.is_ascii_whitespace() {
-                continue;
-            }
-            let digit = (digit - b'0') as i64;
+            .tuple_windows::<(_, _)>()
+            .collect_vec();
 
-            // The number we have to modify is determined by the character position
-            // within the current column.
-            let new_len = j + 1;
-            if v.len() < new_len {
-                v.resize(new_len, 0);
-            }
-            let val = &mut v[j];
+        let mut columns = Vec::new();
 
-            // Build the number digit by digit.
-            *val = *val * 10 + digit;
+        // In addition to the problem columns, each digit is also a column,
+        // where the digit in the first row is the most significant digit
+        // and the digit in the last (non-empty) row is the least significant digit
+        // of a single number.
+        // The amount of numbers in a problem is determined by the maximum amount of (non-empty) digit
+        // columns.
+        for (i, n) in lines[..lines.len() - 1].iter().flat_map(|line| {
+            // Slice each number of the current row by the ranges determined by the operators,
+            // this is possible since all numbers are aligned (and so are the operators).
+            op_ranges
+                .iter()
+                .copied()
+                .enumerate() // enumerate columns
+                .map(|(i, (a, b))| (i, &line[a..b.min(line.len())]))
+        }) {
+            // Get the right column vector.
+            let v = match columns.get_mut(i) {
+                None => {
+                    columns.push(Vec::new());
+                    columns.last_mut().unwrap()
+                }
+                Some(v) => v,
+            };
+
+            // Each digit `j` of the current number `i` within a column in the data with some row index
+            // `r` is actually the `r`-th digit of the `j`-th number within that column i.
+            for (j, digit) in n.bytes().enumerate() {
+                let c = char::from(digit);
+                if c.is_ascii_whitespace() {
+                    continue;
+                }
+                let digit = (digit - b'0') as i64;
+
+                // The number we have to modify is determined by the character position
+                // within the current column.
+                let new_len = j + 1;
+                if v.len() < new_len {
+                    v.resize(new_len, 0);
+                }
+                let val = &mut v[j];
+
+                // Build the number digit by digit.
+                *val = *val * 10 + digit;
+            }
         }
-    }
 
-    let mut total = 0_i64;
-    for (col, (_, op)) in columns.into_iter().zip(ops) {
-        let res = op.apply(&col);
-        println!("{} = {res}", op.format_numbers(&col));
-        total += res;
+        let mut total = 0_i64;
+        for (col, (_, op)) in columns.into_iter().zip(ops.iter().copied()) {
+            let res = op.apply(&col);
+            log::debug!("{} = {res}", op.format_numbers(&col));
+            total += res;
+        }
+        total.into()
     }
-    return total;
 }
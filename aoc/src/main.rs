@@ -0,0 +1,13 @@
+use std::process::ExitCode;
+
+use aoc_common::solution::Years;
+
+const YEARS: &Years = &[
+    (2023, aoc2023::DAYS),
+    (2024, aoc2024::DAYS),
+    (2025, aoc2025::DAYS),
+];
+
+fn main() -> ExitCode {
+    aoc_common::runner::main(YEARS)
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    /// An integer that doesn't fit into an `i64`.
+    BigInt(i128),
+    Str(String),
+    /// A position, printed as `x,y`.
+    Coord(i64, i64),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(i) => write!(f, "{i}"),
+            Answer::BigInt(i) => write!(f, "{i}"),
+            Answer::Str(s) => f.write_str(s),
+            Answer::Coord(x, y) => write!(f, "{x},{y}"),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i64::try_from(value) {
+                        Ok(i) => Answer::Int(i),
+                        Err(_) => Answer::BigInt(value as i128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(i) => Answer::Int(i),
+            Err(_) => Answer::BigInt(value),
+        }
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value).expect("answer too large").into()
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Str(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Str(value.to_owned())
+    }
+}
+
+impl<T: Into<i64>> From<(T, T)> for Answer {
+    fn from((x, y): (T, T)) -> Self {
+        Answer::Coord(x.into(), y.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn from_int() {
+        assert_eq!(Answer::from(42usize), Answer::Int(42));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as i128));
+        assert_eq!(Answer::from((3u32, 4u32)).to_string(), "3,4");
+    }
+}
@@ -0,0 +1,58 @@
+use std::time::{Duration, Instant};
+
+use crate::solution::{DynSolution, Part};
+
+/// Number of untimed iterations run before the measured ones.
+const WARM_UP_RUNS: usize = 1;
+
+/// Parse `data` and solve `parts` `runs` times, then print the min/median/max time of the parse
+/// and of every part.
+pub fn bench(
+    solution: &dyn DynSolution,
+    parts: &[Part],
+    data: &str,
+    runs: usize,
+) -> anyhow::Result<()> {
+    let mut parse_times = Vec::with_capacity(runs);
+    let mut part_times = vec![Vec::with_capacity(runs); parts.len()];
+
+    for i in 0..WARM_UP_RUNS + runs {
+        let start = Instant::now();
+        let input = solution.parse_dyn(data)?;
+        let parse_time = start.elapsed();
+
+        let times = parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                std::hint::black_box(solution.solve_dyn(&*input, part));
+                start.elapsed()
+            })
+            .collect::<Vec<_>>();
+
+        if i >= WARM_UP_RUNS {
+            parse_times.push(parse_time);
+            for (part_times, time) in part_times.iter_mut().zip(times) {
+                part_times.push(time);
+            }
+        }
+    }
+
+    println!("{runs} runs after {WARM_UP_RUNS} warm-up run:");
+    println!(
+        "{:<6} | {:>12} | {:>12} | {:>12}",
+        "", "min", "median", "max"
+    );
+    print_stats("parse", &mut parse_times);
+    for (part, times) in parts.iter().zip(&mut part_times) {
+        print_stats(&format!("part {}", *part as u8), times);
+    }
+    Ok(())
+}
+
+fn print_stats(label: &str, times: &mut [Duration]) {
+    times.sort_unstable();
+    let [min, median, max] =
+        [0, times.len() / 2, times.len() - 1].map(|i| format!("{:.2?}", times[i]));
+    println!("{label:<6} | {min:>12} | {median:>12} | {max:>12}");
+}
@@ -0,0 +1,120 @@
+//! Discovery of the `dayN.rs` modules of a year, used by the build scripts of the year crates.
+
+use std::{fmt::Write, path::Path};
+
+use syn::{Fields, ImplItem, Item, Type, Visibility};
+
+/// Scan `src/` of the crate being built for `dayN.rs` files and generate `$OUT_DIR/days.rs`,
+/// which declares them as public modules `dayN` and the `DAYS` dispatch table.
+///
+/// Every `dayN.rs` must define `pub struct DayN` implementing [`Solution`](crate::Solution). A
+/// unit struct is used as is, a struct with fields (the parameters of the day) must provide the
+/// defaults as `pub const DEFAULT: Self`.
+///
+/// `cargo fmt` doesn't reach the generated modules, a test of this crate checks their formatting
+/// with rustfmt instead.
+///
+/// # Panics
+/// If a day module doesn't follow these rules, which fails the build.
+pub fn generate_days() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("not run by cargo");
+    let out_dir = std::env::var("OUT_DIR").expect("not run by cargo");
+    let src_dir = Path::new(&manifest_dir).join("src");
+    println!("cargo:rerun-if-changed={}", src_dir.display());
+
+    let mut days = std::fs::read_dir(&src_dir)
+        .unwrap_or_else(|e| panic!("failed to read '{}': {e}", src_dir.display()))
+        .filter_map(|entry| {
+            let path = entry.expect("failed to read directory entry").path();
+            let day = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix("day")?
+                .strip_suffix(".rs")?
+                .parse::<u32>()
+                .ok()?;
+            Some((day, path))
+        })
+        .collect::<Vec<_>>();
+    days.sort_by_key(|(day, _)| *day);
+
+    let mut modules = String::new();
+    let mut table = String::new();
+    for (day, path) in &days {
+        let solution = parse_file(path)
+            .and_then(|file| solution_expr(*day, &file.items))
+            .unwrap_or_else(|e| panic!("invalid day module '{}': {e}", path.display()));
+
+        writeln!(
+            modules,
+            "#[path = {:?}]\npub mod day{day};",
+            path.display().to_string()
+        )
+        .unwrap();
+        writeln!(table, "    ({day}, &day{day}::{solution}),").unwrap();
+    }
+
+    let generated = format!(
+        "{modules}\n/// All implemented days of the year, ordered by day.\n\
+         pub const DAYS: &aoc_common::solution::Days = &[\n{table}];\n"
+    );
+    std::fs::write(Path::new(&out_dir).join("days.rs"), generated)
+        .expect("failed to write days.rs");
+}
+
+fn parse_file(path: &Path) -> Result<syn::File, String> {
+    let source = std::fs::read_to_string(path).map_err(|e| format!("failed to read it: {e}"))?;
+    syn::parse_file(&source).map_err(|e| {
+        let start = e.span().start();
+        format!("{}:{}: {e}", start.line, start.column + 1)
+    })
+}
+
+/// The expression of the solution of `day` in its module with the top level `items`, checking
+/// that they define it as expected by [`generate_days`].
+fn solution_expr(day: u32, items: &[Item]) -> Result<String, String> {
+    let name = format!("Day{day}");
+    let is_day = |ty: &Type| matches!(ty, Type::Path(p) if p.path.is_ident(&name));
+
+    let implements_solution = items.iter().any(|item| match item {
+        Item::Impl(imp) => {
+            let trait_name = imp
+                .trait_
+                .as_ref()
+                .and_then(|(_, path, _)| path.segments.last());
+            trait_name.is_some_and(|t| t.ident == "Solution") && is_day(&imp.self_ty)
+        }
+        _ => false,
+    });
+    if !implements_solution {
+        return Err(format!("expected `impl Solution for {name}`"));
+    }
+
+    let strukt = items.iter().find_map(|item| match item {
+        Item::Struct(s) if s.ident == name => Some(s),
+        _ => None,
+    });
+    let Some(strukt) = strukt.filter(|s| is_pub(&s.vis)) else {
+        return Err(format!("expected `pub struct {name}`"));
+    };
+    if let Fields::Unit = strukt.fields {
+        return Ok(name);
+    }
+
+    let has_default = items.iter().any(|item| match item {
+        Item::Impl(imp) if imp.trait_.is_none() && is_day(&imp.self_ty) => imp.items.iter().any(
+            |item| matches!(item, ImplItem::Const(c) if c.ident == "DEFAULT" && is_pub(&c.vis)),
+        ),
+        _ => false,
+    });
+    if !has_default {
+        return Err(format!(
+            "`{name}` has parameters but no `pub const DEFAULT: Self` with their defaults"
+        ));
+    }
+    Ok(format!("{name}::DEFAULT"))
+}
+
+fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
@@ -0,0 +1,342 @@
+//! Directions on a grid and sets of them.
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{BitAnd, BitOr},
+};
+
+use crate::vec2::Vec2;
+
+/// A turn by 90° to either side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    /// The turn for `L` or `R`.
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'L' => Some(Self::Left),
+            'R' => Some(Self::Right),
+            _ => None,
+        }
+    }
+}
+
+/// One of the four orthogonal directions, up being towards `y = 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up = 0,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// All directions clockwise starting up, in the order of their `as usize` values.
+    pub const ALL: [Dir4; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Rotate this direction by `quarter_turns` times 90° clockwise, counterclockwise when
+    /// negative.
+    pub fn rotate(self, quarter_turns: i32) -> Dir4 {
+        Self::ALL[(self as i32 + quarter_turns).rem_euclid(4) as usize]
+    }
+
+    /// Turn this direction 90° to the side of `turn`.
+    pub fn turn(self, turn: Turn) -> Dir4 {
+        match turn {
+            Turn::Left => self.rotate(-1),
+            Turn::Right => self.rotate(1),
+        }
+    }
+
+    pub fn opposite(self) -> Dir4 {
+        self.rotate(2)
+    }
+
+    /// The offset of one step in this direction.
+    pub fn to_vec2(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2(0, -1),
+            Self::Right => Vec2(1, 0),
+            Self::Down => Vec2(0, 1),
+            Self::Left => Vec2(-1, 0),
+        }
+    }
+
+    /// The arrow `^`, `>`, `v` or `<` pointing in this direction.
+    pub fn glyph(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+
+    /// The direction of the arrow `c` as shown by [`Dir4::glyph`].
+    pub fn from_glyph(c: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.glyph() == c)
+    }
+}
+
+impl From<Dir4> for Vec2 {
+    fn from(dir: Dir4) -> Self {
+        dir.to_vec2()
+    }
+}
+
+/// One of the eight directions including the diagonal ones, up being towards `y = 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    Up = 0,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// All directions clockwise starting up, in the order of their `as usize` values.
+    pub const ALL: [Dir8; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Rotate this direction by `eighth_turns` times 45° clockwise, counterclockwise when
+    /// negative.
+    pub fn rotate(self, eighth_turns: i32) -> Dir8 {
+        Self::ALL[(self as i32 + eighth_turns).rem_euclid(8) as usize]
+    }
+
+    pub fn opposite(self) -> Dir8 {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// The offset of one step in this direction.
+    pub fn to_vec2(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2(0, -1),
+            Self::UpRight => Vec2(1, -1),
+            Self::Right => Vec2(1, 0),
+            Self::DownRight => Vec2(1, 1),
+            Self::Down => Vec2(0, 1),
+            Self::DownLeft => Vec2(-1, 1),
+            Self::Left => Vec2(-1, 0),
+            Self::UpLeft => Vec2(-1, -1),
+        }
+    }
+
+    /// The arrow `↑`, `↗`, `→`, `↘`, `↓`, `↙`, `←` or `↖` pointing in this direction.
+    pub fn glyph(self) -> char {
+        match self {
+            Self::Up => '↑',
+            Self::UpRight => '↗',
+            Self::Right => '→',
+            Self::DownRight => '↘',
+            Self::Down => '↓',
+            Self::DownLeft => '↙',
+            Self::Left => '←',
+            Self::UpLeft => '↖',
+        }
+    }
+
+    /// The direction of the arrow `c` as shown by [`Dir8::glyph`].
+    pub fn from_glyph(c: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.glyph() == c)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::ALL[dir as usize * 2]
+    }
+}
+
+impl From<Dir8> for Vec2 {
+    fn from(dir: Dir8) -> Self {
+        dir.to_vec2()
+    }
+}
+
+/// A direction that a [`DirSet`] can hold.
+pub trait Direction: Copy + 'static {
+    /// All directions, the index of a direction in it is its bit in a [`DirSet`].
+    const ALL: &'static [Self];
+
+    fn index(self) -> usize;
+}
+
+impl Direction for Dir4 {
+    const ALL: &'static [Self] = &Dir4::ALL;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Direction for Dir8 {
+    const ALL: &'static [Self] = &Dir8::ALL;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A set of directions stored as the bits of one byte, like the directions a cell was already
+/// passed in.
+///
+/// ```
+/// use aoc_common::dir::{Dir4, DirSet};
+///
+/// let mut passed = DirSet::from([Dir4::Up]);
+/// assert!(passed.insert(Dir4::Left));
+/// assert!(!passed.insert(Dir4::Up));
+/// assert_eq!(passed.iter().collect::<Vec<_>>(), [Dir4::Up, Dir4::Left]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirSet<D = Dir4> {
+    bits: u8,
+    dir: PhantomData<D>,
+}
+
+impl<D: Direction> DirSet<D> {
+    pub const fn new() -> Self {
+        DirSet {
+            bits: 0,
+            dir: PhantomData,
+        }
+    }
+
+    pub fn contains(self, dir: D) -> bool {
+        self.bits & Self::bit(dir) != 0
+    }
+
+    /// Add `dir` to the set, returns whether it wasn't in it before.
+    pub fn insert(&mut self, dir: D) -> bool {
+        let added = !self.contains(dir);
+        self.bits |= Self::bit(dir);
+        added
+    }
+
+    /// Remove `dir` from the set, returns whether it was in it.
+    pub fn remove(&mut self, dir: D) -> bool {
+        let removed = self.contains(dir);
+        self.bits &= !Self::bit(dir);
+        removed
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+
+    pub fn len(self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// The directions in the set in the order of [`Direction::ALL`].
+    pub fn iter(self) -> impl Iterator<Item = D> {
+        D::ALL.iter().copied().filter(move |&d| self.contains(d))
+    }
+
+    fn bit(dir: D) -> u8 {
+        1 << dir.index()
+    }
+}
+
+impl<D: Direction> Default for DirSet<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Direction + fmt::Debug> fmt::Debug for DirSet<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<D: Direction> FromIterator<D> for DirSet<D> {
+    fn from_iter<I: IntoIterator<Item = D>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for dir in iter {
+            set.insert(dir);
+        }
+        set
+    }
+}
+
+impl<D: Direction, const N: usize> From<[D; N]> for DirSet<D> {
+    fn from(dirs: [D; N]) -> Self {
+        dirs.into_iter().collect()
+    }
+}
+
+/// The union of both sets.
+impl<D: Direction> BitOr for DirSet<D> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        DirSet {
+            bits: self.bits | rhs.bits,
+            dir: PhantomData,
+        }
+    }
+}
+
+/// The intersection of both sets.
+impl<D: Direction> BitAnd for DirSet<D> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        DirSet {
+            bits: self.bits & rhs.bits,
+            dir: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation() {
+        assert_eq!(Dir4::Up.rotate(-1), Dir4::Left);
+        assert_eq!(Dir4::Left.rotate(6), Dir4::Right);
+        assert_eq!(Dir4::Down.turn(Turn::Right), Dir4::Left);
+        assert_eq!(Dir8::UpLeft.rotate(3), Dir8::Right);
+        assert_eq!(Dir8::from(Dir4::Left).opposite(), Dir8::Right);
+        for dir in Dir8::ALL {
+            assert_eq!(dir.opposite().to_vec2(), -dir.to_vec2());
+            assert_eq!(Dir8::from_glyph(dir.glyph()), Some(dir));
+        }
+    }
+
+    #[test]
+    fn set() {
+        let mut set = DirSet::<Dir8>::from([Dir8::Down, Dir8::UpLeft]);
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(Dir8::Down));
+        assert!(!set.contains(Dir8::Down));
+        let horizontal = DirSet::from([Dir4::Left, Dir4::Right]);
+        assert!((horizontal & DirSet::from([Dir4::Up])).is_empty());
+        assert_eq!((horizontal | DirSet::from([Dir4::Up])).len(), 3);
+    }
+}
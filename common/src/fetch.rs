@@ -0,0 +1,166 @@
+//! Downloading the inputs into the input directory.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::input;
+
+/// Environment variable with the session token of the Advent of Code account.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable overriding the URL of the Advent of Code server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (input downloader)"
+);
+
+/// The config file with the `session` token and `base_url` if they aren't set by [`SESSION_VAR`]
+/// and [`BASE_URL_VAR`]: `aoc.toml` at the root of the repository.
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "http://localhost:8000"
+/// ```
+pub fn config_path() -> PathBuf {
+    crate::repo_dir().join("aoc.toml")
+}
+
+/// The settings of the downloader, from the environment or else the [`config_path`].
+struct Config {
+    session: Option<String>,
+    base_url: String,
+}
+
+impl Config {
+    fn load() -> anyhow::Result<Self> {
+        let path = config_path();
+        let file = if path.exists() {
+            let data = std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read '{}'", path.display()))?;
+            data.parse::<toml::Table>()
+                .with_context(|| format!("invalid config file '{}'", path.display()))?
+        } else {
+            toml::Table::new()
+        };
+        let setting = |var: &str, key: &str| -> anyhow::Result<Option<String>> {
+            if let Ok(value) = std::env::var(var) {
+                return Ok(Some(value));
+            }
+            match file.get(key) {
+                Some(toml::Value::String(value)) => Ok(Some(value.clone())),
+                Some(v) => {
+                    anyhow::bail!("`{key}` in '{}' must be a string, not {v}", path.display())
+                }
+                None => Ok(None),
+            }
+        };
+
+        Ok(Config {
+            session: setting(SESSION_VAR, "session")?,
+            base_url: setting(BASE_URL_VAR, "base_url")?
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_owned()),
+        })
+    }
+}
+
+/// Download the input of `day` of `year` to its conventional path in the input directory.
+///
+/// An input that is already there is never downloaded again. An empty file isn't an input but the
+/// placeholder created by the `new` command, it is replaced.
+pub fn fetch(year: u32, day: u32) -> anyhow::Result<()> {
+    let path = input::input_path(year, day, None);
+    if std::fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+        println!("{} is already downloaded", path.display());
+        return Ok(());
+    }
+
+    let config = Config::load()?;
+    let session = config.session.with_context(|| {
+        format!(
+            "no session token, set {SESSION_VAR} or `session` in '{}'",
+            config_path().display()
+        )
+    })?;
+
+    let url = format!(
+        "{}/{year}/day/{day}/input",
+        config.base_url.trim_end_matches('/')
+    );
+    log::info!("downloading {url}");
+    let data = match ureq::get(&url)
+        .set("Cookie", &format!("session={}", session.trim()))
+        .set("User-Agent", USER_AGENT)
+        .call()
+    {
+        Ok(response) => response
+            .into_string()
+            .with_context(|| format!("failed to read the response of {url}"))?,
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            anyhow::bail!("failed to download {url}: status {code}: {}", body.trim());
+        }
+        Err(e) => return Err(e).with_context(|| format!("failed to download {url}")),
+    };
+    anyhow::ensure!(!data.is_empty(), "the input downloaded from {url} is empty");
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create '{}'", dir.display()))?;
+    }
+    std::fs::write(&path, data).with_context(|| format!("failed to write '{}'", path.display()))?;
+    println!("downloaded {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    #[test]
+    fn fetch_from_stub_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        // Answer exactly one request, returning its head.
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut head = String::new();
+            while reader.read_line(&mut head).unwrap() > 2 {}
+            let response = "HTTP/1.1 200 OK\r\nContent-Length: 4\r\nConnection: close\r\n\r\n1 2\n";
+            (&stream).write_all(response.as_bytes()).unwrap();
+            (head, listener)
+        });
+
+        let input_dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        std::env::set_var(input::INPUT_DIR_VAR, &input_dir);
+        std::env::set_var(BASE_URL_VAR, base_url);
+        std::env::set_var(SESSION_VAR, "abc123");
+
+        fetch(2024, 5).unwrap();
+        let (head, listener) = server.join().unwrap();
+        assert!(head.starts_with("GET /2024/day/5/input "), "{head}");
+        assert!(head.contains("\r\nCookie: session=abc123\r\n"), "{head}");
+        let path = input_dir.join("2024").join("day05.txt");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1 2\n");
+
+        // The cached input is used without contacting the server.
+        fetch(2024, 5).unwrap();
+        listener.set_nonblocking(true).unwrap();
+        assert!(listener.accept().is_err());
+
+        std::fs::remove_dir_all(input_dir).unwrap();
+    }
+}
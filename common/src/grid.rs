@@ -0,0 +1,355 @@
+//! Rectangular grids of cells, the maps most of the puzzles take place on.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::{parse, vec2::Vec2};
+
+/// A rectangular grid of cells stored row by row, indexed by [`Vec2`] positions with `(0, 0)`
+/// at the top left.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with `value` in every cell.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// A grid `width` cells wide from its `cells` row by row.
+    ///
+    /// # Panics
+    /// If the number of cells isn't a multiple of `width`.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "the cells of a grid must fill its rows"
+        );
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parse the grid drawn by the non-empty lines of `data`, turning every character into a
+    /// cell with `cell`, which returns [`None`] for an invalid character.
+    ///
+    /// The positions of the `markers` are returned as well, characters that must occur exactly
+    /// once in the grid, like the start of a maze. They are turned into cells like any other.
+    ///
+    /// ```
+    /// use aoc_common::{grid::Grid, vec2::Vec2};
+    ///
+    /// let (maze, [start]) = Grid::parse("#S.\n#.#\n", ['S'], |c| match c {
+    ///     '#' => Some(false),
+    ///     '.' | 'S' => Some(true),
+    ///     _ => None,
+    /// })
+    /// .unwrap();
+    /// assert_eq!(start, Vec2(1, 0));
+    /// assert_eq!(maze.get(Vec2(2, 1)), Some(&false));
+    /// ```
+    pub fn parse<const N: usize>(
+        data: &str,
+        markers: [char; N],
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> anyhow::Result<(Self, [Vec2; N])> {
+        let mut found = [None; N];
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in data.lines().filter(|l| !l.is_empty()).enumerate() {
+            let line_width = line.chars().count();
+            let width = *width.get_or_insert(line_width);
+            if line_width != width {
+                let msg = format!("expected a line of length {width}");
+                return Err(parse::error(data, line, msg));
+            }
+            for (x, (c, at)) in parse::chars(line).enumerate() {
+                if let Some(i) = markers.iter().position(|&m| m == c) {
+                    if found[i].is_some() {
+                        return Err(parse::error(data, at, format!("more than one '{c}'")));
+                    }
+                    found[i] = Some(Vec2(x as i32, y as i32));
+                }
+                let cell = cell(c)
+                    .ok_or_else(|| parse::error(data, at, format!("unexpected char '{c}'")))?;
+                cells.push(cell);
+            }
+            height += 1;
+        }
+        let width = width.ok_or_else(|| anyhow::anyhow!("the map is empty"))?;
+
+        let mut positions = [Vec2::default(); N];
+        for ((pos, found), marker) in positions.iter_mut().zip(found).zip(markers) {
+            *pos = found.ok_or_else(|| anyhow::anyhow!("no '{marker}' in the map"))?;
+        }
+
+        let grid = Grid {
+            cells,
+            width,
+            height,
+        };
+        Ok((grid, positions))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `pos` is inside the grid.
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.to_index(self.width, self.height).is_some()
+    }
+
+    /// The cell at `pos`, [`None`] outside the grid.
+    pub fn get(&self, pos: Vec2) -> Option<&T> {
+        let idx = pos.to_index(self.width, self.height)?;
+        Some(&self.cells[idx])
+    }
+
+    /// The cell at `pos`, [`None`] outside the grid.
+    pub fn get_mut(&mut self, pos: Vec2) -> Option<&mut T> {
+        let idx = pos.to_index(self.width, self.height)?;
+        Some(&mut self.cells[idx])
+    }
+
+    /// All cells row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// All cells row by row.
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// All positions row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Vec2> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Vec2(x as i32, y as i32)))
+    }
+
+    /// All cells with their positions row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// All cells with their positions row by row.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vec2, &mut T)> {
+        self.positions().zip(&mut self.cells)
+    }
+
+    /// The row at `y`.
+    ///
+    /// # Panics
+    /// If `y` is outside the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is outside the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// All rows from the top.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks_exact` panics on a chunk size of 0.
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// All rows from the top.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.cells.chunks_exact_mut(self.width.max(1))
+    }
+
+    /// The cells of the column at `x` from the top.
+    ///
+    /// # Panics
+    /// If `x` is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// All columns from the left.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The positions of the up to four orthogonal neighbors of `pos` inside the grid, clockwise
+    /// starting up.
+    pub fn neighbors4(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        pos.neighbors4().filter(|&n| self.contains(n))
+    }
+
+    /// The positions of the up to eight neighbors of `pos` inside the grid including the
+    /// diagonal ones, clockwise starting up.
+    pub fn neighbors8(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        pos.neighbors8().filter(|&n| self.contains(n))
+    }
+
+    /// A grid with the cells turned into `f(cell)`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The grid mirrored along its diagonal, the rows becoming the columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flatten().cloned().collect();
+        Grid {
+            cells,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// The grid rotated by 90° clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self
+            .columns()
+            .flat_map(|column| column.collect::<Vec<_>>().into_iter().rev())
+            .cloned()
+            .collect();
+        Grid {
+            cells,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// The grid rotated by 90° counterclockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x))
+            .cloned()
+            .collect();
+        Grid {
+            cells,
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+impl<T> Index<Vec2> for Grid<T> {
+    type Output = T;
+
+    /// The cell at `pos`, panicking outside the grid.
+    fn index(&self, pos: Vec2) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos:?} is outside the {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Vec2> for Grid<T> {
+    /// The cell at `pos`, panicking outside the grid.
+    fn index_mut(&mut self, pos: Vec2) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos:?} is outside the {width}x{height} grid"),
+        }
+    }
+}
+
+/// The cells of every row next to each other, the rows on separate lines.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(data: &str) -> Grid<u32> {
+        Grid::parse(data, [], |c| c.to_digit(10)).unwrap().0
+    }
+
+    #[test]
+    fn parse() {
+        let data = "S12\n3E4\n";
+        let (grid, [start, end]) =
+            Grid::parse(data, ['S', 'E'], |c| c.to_digit(10).or(Some(0))).unwrap();
+        assert_eq!((start, end), (Vec2(0, 0), Vec2(1, 1)));
+        assert_eq!(grid.to_string(), "012\n304");
+
+        let e = Grid::parse("12\n345\n", [], |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 1: expected a line of length 2"
+        );
+        let e = Grid::parse("12\n3x\n", [], |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 2: unexpected char 'x'");
+        let e = Grid::parse("S1\n2S\n", ['S'], |_| Some(())).unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 2: more than one 'S'");
+        let e = Grid::parse("12\n", ['S'], |_| Some(())).unwrap_err();
+        assert_eq!(e.to_string(), "no 'S' in the map");
+    }
+
+    #[test]
+    fn access() {
+        let grid = digits("123\n456\n");
+        assert_eq!(grid.get(Vec2(2, 1)), Some(&6));
+        assert_eq!(grid.get(Vec2(3, 0)), None);
+        assert_eq!(grid.get(Vec2(0, -1)), None);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(
+            grid.neighbors4(Vec2(0, 0)).collect::<Vec<_>>(),
+            [Vec2(1, 0), Vec2(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(Vec2(2, 0)).count(), 3);
+    }
+
+    #[test]
+    fn transform() {
+        let grid = digits("123\n456\n");
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_cw().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_ccw().to_string(), "36\n25\n14");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+}
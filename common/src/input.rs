@@ -0,0 +1,93 @@
+use std::{
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+/// Environment variable overriding the directory with the inputs of all years.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The directory with the inputs of all years, `inputs/` at the root of the repository unless
+/// overridden by [`INPUT_DIR_VAR`].
+pub fn input_dir() -> PathBuf {
+    match std::env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => dir.into(),
+        None => crate::repo_dir().join("inputs"),
+    }
+}
+
+/// The conventional path of the input of `day`: `<year>/dayNN.txt` in the [`input_dir`], or
+/// `<year>/dayNN.example<K>.txt` for the `example` K.
+pub fn input_path(year: u32, day: u32, example: Option<u32>) -> PathBuf {
+    let file = match example {
+        Some(k) => format!("day{day:02}.example{k}.txt"),
+        None => format!("day{day:02}.txt"),
+    };
+    input_dir().join(year.to_string()).join(file)
+}
+
+/// Read the input at `path` and [`normalize`] it, the error names the path.
+pub fn read_input(path: &Path, trim: bool) -> anyhow::Result<String> {
+    let data = std::fs::read_to_string(path).map_err(|e| {
+        let msg = if e.kind() == io::ErrorKind::NotFound {
+            format!("input file '{}' not found", path.display())
+        } else {
+            format!("failed to read the input file '{}'", path.display())
+        };
+        anyhow::Error::new(e).context(msg)
+    })?;
+    Ok(normalize(&data, trim))
+}
+
+/// Whether `error` of [`read_input`] is because the file doesn't exist.
+pub fn is_not_found(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<io::Error>()
+        .is_some_and(|e| e.kind() == io::ErrorKind::NotFound)
+}
+
+/// Read the whole input from stdin and [`normalize`] it.
+pub fn read_stdin(trim: bool) -> anyhow::Result<String> {
+    let mut data = String::new();
+    io::stdin()
+        .read_to_string(&mut data)
+        .context("failed to read the input from stdin")?;
+    Ok(normalize(&data, trim))
+}
+
+/// Clean up an input the way it was saved so that the solutions don't have to: strip a byte
+/// order mark and convert CRLF line endings to LF.
+///
+/// With `trim`, also remove the trailing whitespace of every line and the blank lines at the end,
+/// leaving a single newline after the last line.
+pub fn normalize(data: &str, trim: bool) -> String {
+    let data = data.strip_prefix('\u{feff}').unwrap_or(data);
+    if !trim {
+        return data.replace("\r\n", "\n");
+    }
+
+    let mut normalized = String::with_capacity(data.len());
+    for line in data.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    normalized.truncate(normalized.trim_end().len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_input() {
+        let data = "\u{feff}1 2 \r\n\r\n3\t\r\n\r\n  \n";
+        assert_eq!(normalize(data, false), "1 2 \n\n3\t\n\n  \n");
+        assert_eq!(normalize(data, true), "1 2\n\n3\n");
+        assert_eq!(normalize("\n\n", true), "");
+    }
+}
@@ -0,0 +1,74 @@
+pub mod answer;
+pub mod bench;
+#[cfg(feature = "build")]
+pub mod build;
+pub mod dir;
+pub mod fetch;
+pub mod grid;
+pub mod input;
+pub mod logger;
+pub mod parse;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod vec2;
+pub mod verify;
+#[cfg(target_os = "linux")]
+pub mod watch;
+
+pub use answer::Answer;
+pub use solution::{DynSolution, Part, Solution};
+
+/// The root directory of the repository.
+fn repo_dir() -> &'static std::path::Path {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the common crate is inside the repository")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    /// `cargo fmt` doesn't reach the day modules declared by the build scripts, check them with
+    /// rustfmt directly.
+    #[test]
+    fn day_modules_are_formatted() {
+        if Command::new("rustfmt").arg("--version").output().is_err() {
+            eprintln!("rustfmt not found, skipping");
+            return;
+        }
+
+        let mut unformatted = vec![];
+        for entry in std::fs::read_dir(super::repo_dir()).unwrap() {
+            let crate_dir = entry.unwrap().path();
+            let Ok(manifest) = std::fs::read_to_string(crate_dir.join("Cargo.toml")) else {
+                continue;
+            };
+            let Some(edition) = manifest.lines().find_map(|l| {
+                let edition = l.strip_prefix("edition = \"")?.strip_suffix('"')?;
+                Some(edition.to_owned())
+            }) else {
+                continue;
+            };
+
+            for file in std::fs::read_dir(crate_dir.join("src")).unwrap() {
+                let path = file.unwrap().path();
+                let name = path.file_name().unwrap().to_string_lossy();
+                if !(name.starts_with("day") && name.ends_with(".rs")) {
+                    continue;
+                }
+                let status = Command::new("rustfmt")
+                    .args(["--check", "--edition", &edition])
+                    .arg(&path)
+                    .output()
+                    .unwrap()
+                    .status;
+                if !status.success() {
+                    unformatted.push(path);
+                }
+            }
+        }
+        assert!(unformatted.is_empty(), "not formatted: {unformatted:?}");
+    }
+}
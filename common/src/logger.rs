@@ -0,0 +1,40 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Logger writing all records up to the max level to stderr, so that stdout only has the answers.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let target = record.target();
+        let target = target.split_once("::").map_or(target, |(_, module)| module);
+        match record.level() {
+            Level::Error | Level::Warn => {
+                eprintln!("{} {target}: {}", record.level(), record.args())
+            }
+            _ => eprintln!("[{target}] {}", record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install the stderr logger, logging errors only if `quiet`, otherwise warnings and one level more
+/// for every step of `verbosity`.
+pub fn init(quiet: bool, verbosity: u8) {
+    let level = match (quiet, verbosity) {
+        (true, _) => LevelFilter::Error,
+        (false, 0) => LevelFilter::Warn,
+        (false, 1) => LevelFilter::Info,
+        (false, 2) => LevelFilter::Debug,
+        (false, _) => LevelFilter::Trace,
+    };
+    log::set_logger(&StderrLogger).expect("logger already initialized");
+    log::set_max_level(level);
+}
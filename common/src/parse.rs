@@ -0,0 +1,305 @@
+//! Parsing of the inputs: errors that point at the offending part of the input, and
+//! [nom](https://docs.rs/nom/7) combinators for the pieces most inputs are made of.
+//!
+//! A day can declare the grammar of its input with the combinators and run it with [`all`]:
+//! ```
+//! use aoc_common::parse::{self, comma_list, key_value, uint};
+//! use nom::{bytes::complete::tag, sequence::separated_pair};
+//!
+//! let data = "Register A: 729\n\nProgram: 0,1,5\n";
+//! let grammar = separated_pair(
+//!     key_value(tag("Register A"), uint::<i64>),
+//!     parse::section_break,
+//!     key_value(tag("Program"), comma_list(uint::<u8>)),
+//! );
+//! let ((_, a), (_, program)) = parse::all(data, grammar).unwrap();
+//! assert_eq!((a, program), (729, vec![0, 1, 5]));
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, multispace0, space0, space1},
+    combinator::{eof, map_res, opt, recognize},
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    sequence::{pair, preceded, separated_pair, terminated},
+    Parser,
+};
+
+/// An error at a location of the input, with the line it is on as snippet.
+///
+/// It is displayed as `line 3, column 4: <message>`, [`ParseError::snippet`] shows the location
+/// in the line.
+#[derive(Debug)]
+pub struct ParseError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: usize,
+    /// The whole line of the error.
+    pub text: String,
+    /// The length of the offending part of the line in characters.
+    len: usize,
+    msg: String,
+}
+
+impl ParseError {
+    /// An error `msg` about `at`, a substring of the whole input `data` as returned by `lines`,
+    /// `split`, `trim` and the like.
+    ///
+    /// # Panics
+    /// If `at` isn't a part of `data`.
+    pub fn new(data: &str, at: &str, msg: impl fmt::Display) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(data.as_ptr() as usize)
+            .filter(|offset| offset + at.len() <= data.len())
+            .expect("the location of a parse error must be a part of the input");
+
+        let line_start = data[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = data[offset..].find('\n').map_or(data.len(), |i| offset + i);
+        let text = &data[line_start..line_end];
+        let at = &data[offset..(offset + at.len()).min(line_end)];
+
+        ParseError {
+            line: data[..line_start].matches('\n').count() + 1,
+            column: data[line_start..offset].chars().count() + 1,
+            text: text.trim_end_matches('\r').to_owned(),
+            len: at.chars().count().max(1),
+            msg: msg.to_string(),
+        }
+    }
+
+    /// The line of the error with the offending part underlined:
+    /// ```text
+    ///   |
+    /// 3 | 12x4
+    ///   |   ^
+    /// ```
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let margin = " ".repeat(number.len());
+        format!(
+            "{margin} |\n{number} | {}\n{margin} | {}{}",
+            self.text,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.len)
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.msg
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A [`ParseError`] `msg` about `at`, a substring of the whole input `data`.
+pub fn error(data: &str, at: &str, msg: impl fmt::Display) -> anyhow::Error {
+    ParseError::new(data, at, msg).into()
+}
+
+/// Parse `s`, a substring of the whole input `data`, failing with a [`ParseError`] at `s`.
+pub fn from_str<T>(data: &str, s: &str) -> anyhow::Result<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    s.parse()
+        .map_err(|e| error(data, s, format_args!("invalid value '{s}': {e}")))
+}
+
+/// The characters of `s` with their substrings of `s`, to point a [`ParseError`] at one of them.
+pub fn chars(s: &str) -> impl Iterator<Item = (char, &str)> {
+    s.char_indices().map(|(i, c)| (c, &s[i..i + c.len_utf8()]))
+}
+
+/// The result of the parsers of the toolkit, its errors keep the [`nom::error::context`] they
+/// happened in.
+pub type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// Parse the whole input `data` with `parser`, allowing trailing whitespace.
+///
+/// A failure is a [`ParseError`] at the character the parser got stuck on, naming what it
+/// expected there and the [`nom::error::context`]s it was in.
+pub fn all<'a, O>(
+    data: &'a str,
+    parser: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> anyhow::Result<O> {
+    let mut parser = terminated(parser, pair(multispace0, eof));
+    match parser.parse(data) {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            let (at, kind) = e.errors.first().expect("a nom error has a kind");
+            let mut contexts = e.errors[1..]
+                .iter()
+                .filter_map(|(input, kind)| match kind {
+                    VerboseErrorKind::Context(context) => Some((*input, context)),
+                    _ => None,
+                })
+                .peekable();
+
+            // A context that starts where the error is names what was expected better than the
+            // kind of the failed parser.
+            let mut msg = match contexts.next_if(|(input, _)| input.as_ptr() == at.as_ptr()) {
+                Some((_, context)) => format!("expected {context}"),
+                None => match kind {
+                    VerboseErrorKind::Char(c) => format!("expected '{}'", c.escape_default()),
+                    VerboseErrorKind::Nom(ErrorKind::MapRes) => "invalid number".to_owned(),
+                    VerboseErrorKind::Nom(ErrorKind::Eof) => "unexpected trailing input".to_owned(),
+                    VerboseErrorKind::Nom(kind) => {
+                        format!("expected {}", kind.description().to_lowercase())
+                    }
+                    VerboseErrorKind::Context(context) => format!("invalid {context}"),
+                },
+            };
+            for (_, context) in contexts {
+                msg += &format!(" in {context}");
+            }
+
+            let at = chars(at).next().map_or(*at, |(_, c)| c);
+            Err(error(data, at, msg))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(error(data, &data[data.len()..], "incomplete input")),
+    }
+}
+
+/// A signed integer of any width, like `-42`.
+pub fn int<T: FromStr>(input: &str) -> IResult<'_, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+/// An unsigned integer of any width, like `42`.
+pub fn uint<T: FromStr>(input: &str) -> IResult<'_, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// A vector of two signed integers labelled with `label`, like `p=3,-4`.
+pub fn vec2<'a, T: FromStr>(label: &'a str) -> impl FnMut(&'a str) -> IResult<'a, (T, T)> {
+    preceded(
+        pair(tag(label), char('=')),
+        separated_pair(int, char(','), int),
+    )
+}
+
+/// A list of one or more `item`s separated by commas, optionally followed by spaces.
+pub fn comma_list<'a, O>(
+    item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    list(pair(char(','), space0), item, |_| false)
+}
+
+/// A list of one or more `item`s separated by spaces or tabs.
+pub fn ws_list<'a, O>(
+    item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    list(space1, item, at_line_end)
+}
+
+/// A `<key>: <value>` pair, like `Register A: 729`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<&'a str, K, VerboseError<&'a str>>,
+    value: impl Parser<&'a str, V, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, (K, V)> {
+    separated_pair(key, pair(char(':'), space0), value)
+}
+
+/// One or more lines with an `item` each, up to a blank line or the end of the input.
+pub fn lines<'a, O>(
+    item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    list(line_ending, item, at_line_end)
+}
+
+/// The blank line between two sections of the input.
+pub fn section_break(input: &str) -> IResult<'_, ()> {
+    let (input, _) = pair(line_ending, line_ending)(input)?;
+    Ok((input, ()))
+}
+
+/// One or more sections parsed by `section`, separated by blank lines.
+pub fn sections<'a, O>(
+    section: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    list(section_break, section, |rest| rest.trim().is_empty())
+}
+
+/// One or more `item`s separated by `separator`, until `end` accepts the input after a separator.
+///
+/// Unlike [`nom::multi::separated_list1`], an item that fails after a separator fails the whole
+/// parse, so that the error points into the item rather than at the separator.
+fn list<'a, O, S>(
+    mut separator: impl Parser<&'a str, S, VerboseError<&'a str>>,
+    mut item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+    end: fn(&str) -> bool,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    move |input| {
+        let (mut input, first) = item.parse(input)?;
+        let mut items = vec![first];
+        loop {
+            let rest = match separator.parse(input) {
+                Ok((rest, _)) if !end(rest) => rest,
+                Ok(_) | Err(nom::Err::Error(_)) => return Ok((input, items)),
+                Err(e) => return Err(e),
+            };
+            let (rest, next) = item.parse(rest).map_err(|e| match e {
+                nom::Err::Error(e) => nom::Err::Failure(e),
+                e => e,
+            })?;
+            items.push(next);
+            input = rest;
+        }
+    }
+}
+
+/// Whether `rest` is at the end of a line or of the input.
+fn at_line_end(rest: &str) -> bool {
+    rest.is_empty() || rest.starts_with(['\n', '\r'])
+}
+
+/// The snippet of the [`ParseError`] that caused `error`, if any.
+pub fn snippet(error: &anyhow::Error) -> Option<String> {
+    error.downcast_ref::<ParseError>().map(ParseError::snippet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_location() {
+        let data = "1,2\n3,x4\n";
+        let line = data.lines().nth(1).unwrap();
+        let e = from_str::<u32>(data, &line[2..]).unwrap_err();
+
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 3: invalid value 'x4': invalid digit found in string"
+        );
+        assert_eq!(snippet(&e).unwrap(), "  |\n2 | 3,x4\n  |   ^^");
+    }
+
+    #[test]
+    fn grammar() {
+        let grammar = || {
+            sections(lines(pair(
+                vec2::<i32>("p"),
+                preceded(char(' '), vec2::<i32>("v")),
+            )))
+        };
+
+        let data = "p=0,4 v=3,-3\np=6,3 v=-1,-3\n\np=10,3 v=-1,2\n";
+        let robots = all(data, grammar()).unwrap();
+        assert_eq!(robots[0][1], ((6, 3), (-1, -3)));
+        assert_eq!(robots[1], vec![((10, 3), (-1, 2))]);
+
+        let e = all("p=0,4 v=3,-3\np=6,3 v=-1,x\n", grammar()).unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 12: expected digit");
+    }
+}
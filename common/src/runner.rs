@@ -0,0 +1,576 @@
+use std::{
+    any::Any,
+    fmt,
+    num::NonZeroU32,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use clap::Parser;
+use serde_json::{json, Value};
+
+use crate::{
+    bench, fetch, input, logger, parse, scaffold,
+    solution::{Days, DynSolution, Part, Years},
+    verify, Answer,
+};
+
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    /// Only log errors.
+    #[clap(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Log more diagnostic output to stderr, can be repeated (`-v`, `-vv`, `-vvv`).
+    #[clap(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Don't trim the trailing whitespace of the lines and the trailing blank lines of the input.
+    ///
+    /// A byte order mark and CRLF line endings are always removed.
+    #[clap(long, global = true)]
+    no_trim: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Create `src/dayN.rs` of a year from a template, and an empty input file.
+    New {
+        #[clap(short, long)]
+        year: u32,
+        #[clap(short, long)]
+        day: NonZeroU32,
+    },
+    /// Download the input of a day into the input directory, unless it is already there.
+    ///
+    /// An empty input file, as created by `new`, doesn't count as downloaded and is replaced.
+    ///
+    /// The session token is read from the `AOC_SESSION` environment variable or `session` in
+    /// `aoc.toml` at the root of the repository, the server from `AOC_BASE_URL` or `base_url`.
+    Fetch {
+        #[clap(short, long)]
+        year: u32,
+        #[clap(short, long)]
+        day: NonZeroU32,
+    },
+    /// Run the days and compare their answers with the expected ones in `<year>/answers.toml`.
+    ///
+    /// Exits with an error if any answer doesn't match, if an input can't be read, or if there are
+    /// no answers to compare with.
+    Verify {
+        /// Which year to verify. If unspecified, verifies all years.
+        #[clap(short, long)]
+        year: Option<u32>,
+        /// The time limit of every day in seconds, a day running longer fails.
+        ///
+        /// A day that times out isn't stopped, its thread keeps running and using CPU in the
+        /// background until it finishes or the process exits, which slows down the days after it.
+        #[clap(long, value_name = "SECS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+    },
+}
+
+#[derive(clap::Args)]
+struct RunArgs {
+    /// Which year to run. If unspecified, runs the latest year.
+    #[clap(short, long)]
+    year: Option<u32>,
+    /// Which day to run. If unspecified, runs the latest day.
+    #[clap(short, long)]
+    day: Option<NonZeroU32>,
+    /// Which part to run, the input is only parsed once for both parts.
+    #[clap(short, long, value_enum, default_value_t = Parts::Both)]
+    part: Parts,
+    /// How to print the results.
+    #[clap(long, value_enum, default_value_t = Format::Text, conflicts_with = "bench")]
+    format: Format,
+    /// Run every implemented day and print a summary table.
+    #[clap(long, conflicts_with_all = ["day", "file"])]
+    all: bool,
+    /// The time limit of every day in seconds with `--all`, a day running longer fails.
+    ///
+    /// A day that times out isn't stopped, its thread keeps running and using CPU in the background
+    /// until it finishes or the process exits, which slows down the days after it.
+    #[clap(long, value_name = "SECS", value_parser = parse_timeout, requires = "all")]
+    timeout: Option<Duration>,
+    /// Run the selected day this many times and report the min/median/max time of the parse and
+    /// of every part.
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..), conflicts_with = "all")]
+    bench: Option<u32>,
+    /// Override a parameter of the selected day, can be given multiple times.
+    #[clap(long = "param", value_name = "KEY=VALUE", value_parser = parse_param, conflicts_with = "all")]
+    params: Vec<(String, String)>,
+    /// Keep running and run the selected day again every time its input file changes.
+    #[clap(long, conflicts_with_all = ["all", "bench", "format"])]
+    watch: bool,
+    /// Use the example input `dayNN.example<K>.txt` instead of `dayNN.txt`.
+    #[clap(short, long, value_name = "K", conflicts_with = "file")]
+    example: Option<u32>,
+
+    /// The file with the problem input, `-` reads it from stdin.
+    ///
+    /// If unspecified, `<year>/dayNN.txt` in the input directory is used. The input directory is
+    /// `inputs/` at the root of the repository unless overridden by the `AOC_INPUT_DIR`
+    /// environment variable.
+    file: Option<PathBuf>,
+}
+
+fn parse_param(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{s}'"))?;
+    Ok((key.trim().to_owned(), value.trim().to_owned()))
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    let secs = s
+        .parse::<f64>()
+        .map_err(|e| format!("invalid number of seconds '{s}': {e}"))?;
+    Duration::try_from_secs_f64(secs).map_err(|e| format!("invalid timeout '{s}': {e}"))
+}
+
+/// The output formats selected by `--format`.
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// The answers as text, or a table of all days.
+    Text,
+    /// One JSON object per line for every part with its year, day, part, status (`ok`, `error` or
+    /// `not-implemented`), answer and time.
+    Json,
+}
+
+/// The parts selected by `--part`.
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Parts {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+impl Parts {
+    fn parts(self) -> &'static [Part] {
+        match self {
+            Parts::One => &[Part::One],
+            Parts::Two => &[Part::Two],
+            Parts::Both => &[Part::One, Part::Two],
+        }
+    }
+}
+
+/// Parse the command line arguments and run the selected day of the selected year of `years`, or
+/// the selected subcommand.
+///
+/// An error is reported on stderr with its causes, and the location in the input if it is a
+/// [`ParseError`](parse::ParseError).
+pub fn main(years: &Years) -> ExitCode {
+    let cli = Cli::parse();
+    logger::init(cli.quiet, cli.verbose);
+    let result = match cli.command {
+        Some(Command::New { year, day }) => scaffold::new_day(year, day.get()),
+        Some(Command::Fetch { year, day }) => fetch::fetch(year, day.get()),
+        Some(Command::Verify { year, timeout }) => {
+            verify::verify(years, year, timeout, !cli.no_trim)
+        }
+        None => run(years, cli.run, !cli.no_trim),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            report(&e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn report(error: &anyhow::Error) {
+    eprintln!("error: {error}");
+    for cause in error.chain().skip(1) {
+        eprintln!("caused by: {cause}");
+    }
+    if let Some(snippet) = parse::snippet(error) {
+        eprintln!("{snippet}");
+    }
+}
+
+fn run(years: &Years, args: RunArgs, trim: bool) -> anyhow::Result<()> {
+    let &(year, days) = match args.year {
+        Some(y) => years
+            .iter()
+            .find(|(year, _)| *year == y)
+            .with_context(|| format!("year {y} not implemented"))?,
+        None => years.last().context("no years implemented")?,
+    };
+
+    if args.all {
+        let parts = args.part.parts();
+        let example = args.example;
+        return run_all(year, days, parts, example, args.timeout, trim, args.format);
+    }
+
+    let &(day, solution) = match args.day.map(NonZeroU32::get) {
+        Some(d) => days
+            .iter()
+            .find(|(day, _)| *day == d)
+            .with_context(|| format!("day {d} not implemented"))?,
+        None => days.last().context("no days implemented")?,
+    };
+    let solution = &*solution
+        .with_params(&args.params)
+        .with_context(|| format!("invalid parameters for day {day}"))?;
+
+    // `None` reads the input from stdin.
+    let file = match args.file {
+        Some(file) if file.as_os_str() == "-" => None,
+        Some(file) => Some(file),
+        None => Some(input::input_path(year, day, args.example)),
+    };
+
+    if args.watch {
+        let file = file.context("can't watch stdin, the input must be a file")?;
+        return watch(solution, args.part.parts(), &file, trim);
+    }
+
+    let data = match file {
+        Some(file) => input::read_input(&file, trim)?,
+        None => input::read_stdin(trim)?,
+    };
+
+    if let Some(runs) = args.bench {
+        return bench::bench(solution, args.part.parts(), &data, runs as usize);
+    }
+
+    let params = solution.params_dyn();
+    if !params.is_empty() {
+        let params = params.iter().map(|(name, value)| format!("{name}={value}"));
+        log::info!("params: {}", params.collect::<Vec<_>>().join(", "));
+    }
+
+    if let Format::Json = args.format {
+        let _quiet = log_panics();
+        for run in run_day(solution, args.part.parts(), &data) {
+            print_json(year, day, &run);
+        }
+        return Ok(());
+    }
+
+    let input = solution
+        .parse_dyn(&data)
+        .with_context(|| format!("failed to parse the input of day {day}"))?;
+    for &part in args.part.parts() {
+        let result = solution.solve_dyn(&*input, part);
+        println!("part {} = {result}", part as u8);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn watch(
+    solution: &dyn DynSolution,
+    parts: &[Part],
+    file: &Path,
+    trim: bool,
+) -> anyhow::Result<()> {
+    crate::watch::watch(solution, parts, file, trim)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn watch(
+    _solution: &dyn DynSolution,
+    _parts: &[Part],
+    _file: &Path,
+    _trim: bool,
+) -> anyhow::Result<()> {
+    anyhow::bail!("--watch is only supported on Linux")
+}
+
+/// Why a part has no answer.
+#[derive(Clone)]
+pub(crate) enum Failure {
+    MissingInput,
+    /// The input exists but can't be read, with this error.
+    Input(String),
+    /// The parse failed with this error, `snippet` shows its location in the input.
+    Parse {
+        error: String,
+        snippet: Option<String>,
+    },
+    /// The part panicked with `unimplemented!` or `todo!`.
+    NotImplemented,
+    /// The part panicked with this message.
+    Panic(String),
+    /// The day didn't finish within this time limit.
+    Timeout(Duration),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::MissingInput => f.write_str("missing input"),
+            Failure::Input(error) => f.write_str(error),
+            Failure::Parse { error, .. } => write!(f, "parse error: {error}"),
+            Failure::NotImplemented => f.write_str("not implemented"),
+            Failure::Panic(msg) => write!(f, "panicked: {msg}"),
+            Failure::Timeout(limit) => write!(f, "timed out after {limit:.2?}"),
+        }
+    }
+}
+
+/// The result of one part of a day.
+pub(crate) struct PartRun {
+    pub part: Part,
+    pub answer: Result<Answer, Failure>,
+    /// The time of the part including the parse.
+    pub time: Duration,
+}
+
+/// Run `parts` of all `days` with their conventional inputs and print the results in `format`.
+///
+/// A day that panics or runs longer than `timeout` fails without stopping the other days.
+fn run_all(
+    year: u32,
+    days: &Days,
+    parts: &'static [Part],
+    example: Option<u32>,
+    timeout: Option<Duration>,
+    trim: bool,
+    format: Format,
+) -> anyhow::Result<()> {
+    let _quiet = log_panics();
+
+    let mut rows = vec![];
+    for &(day, solution) in days {
+        let file = input::input_path(year, day, example);
+        let runs = match input::read_input(&file, trim) {
+            Ok(data) => run_day_isolated(solution, parts, data, timeout),
+            Err(e) if input::is_not_found(&e) => {
+                failed_parts(parts, Failure::MissingInput, Duration::ZERO)
+            }
+            Err(e) => failed_parts(parts, Failure::Input(format!("{e:#}")), Duration::ZERO),
+        };
+
+        rows.extend(runs.into_iter().map(|run| (day, run)));
+    }
+
+    match format {
+        Format::Text => print_table(&rows),
+        Format::Json => {
+            for (day, run) in &rows {
+                print_json(year, *day, run);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run `parts` of `solution` on `data`, the time of the parse is included in every part.
+///
+/// A failing or panicking parse or a panicking part is reported as the failure of its answer and
+/// doesn't stop the run.
+pub(crate) fn run_day(solution: &dyn DynSolution, parts: &[Part], data: &str) -> Vec<PartRun> {
+    let mut runs = vec![];
+    run_parts(solution, parts, data, |run| runs.push(run));
+    runs
+}
+
+/// Like [`run_day`], but on its own thread and failing the parts that didn't finish within
+/// `timeout`.
+///
+/// A day that times out can't be stopped, its thread keeps running in the background until it
+/// finishes or the process exits.
+pub(crate) fn run_day_isolated(
+    solution: &'static dyn DynSolution,
+    parts: &'static [Part],
+    data: String,
+    timeout: Option<Duration>,
+) -> Vec<PartRun> {
+    let Some(timeout) = timeout else {
+        return run_day(solution, parts, &data);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone if the day timed out, the late results are dropped.
+        run_parts(solution, parts, &data, |run| _ = sender.send(run));
+    });
+
+    let deadline = Instant::now() + timeout;
+    let mut runs = vec![];
+    while runs.len() < parts.len() {
+        let left = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(left) {
+            Ok(run) => runs.push(run),
+            Err(_) => break,
+        }
+    }
+
+    let finished = runs.len();
+    runs.extend(failed_parts(
+        &parts[finished..],
+        Failure::Timeout(timeout),
+        timeout,
+    ));
+    runs
+}
+
+/// Log the panics caught by [`run_day`] at debug level instead of printing them to stderr, their
+/// messages are already part of the results.
+///
+/// The previous panic hook is restored when the returned guard is dropped.
+#[must_use]
+pub(crate) fn log_panics() -> LogPanics {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|info| log::debug!("{info}")));
+    LogPanics(Some(previous))
+}
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send>;
+
+/// Restores the panic hook replaced by [`log_panics`] when dropped.
+pub(crate) struct LogPanics(Option<PanicHook>);
+
+impl Drop for LogPanics {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            panic::set_hook(previous);
+        }
+    }
+}
+
+/// Run `parts` of `solution` on `data` as [`run_day`] does, passing the result of every part to
+/// `report` as soon as it is known.
+fn run_parts(
+    solution: &dyn DynSolution,
+    parts: &[Part],
+    data: &str,
+    mut report: impl FnMut(PartRun),
+) {
+    let start = Instant::now();
+    let input = panic::catch_unwind(AssertUnwindSafe(|| solution.parse_dyn(data)));
+    let parse_time = start.elapsed();
+
+    let input = input
+        .map_err(|payload| panic_failure(&*payload))
+        .and_then(|input| {
+            input.map_err(|e| Failure::Parse {
+                error: format!("{e:#}"),
+                snippet: parse::snippet(&e),
+            })
+        });
+    let input = match input {
+        Ok(input) => input,
+        Err(failure) => {
+            failed_parts(parts, failure, parse_time)
+                .into_iter()
+                .for_each(report);
+            return;
+        }
+    };
+
+    for &part in parts {
+        let start = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| solution.solve_dyn(&*input, part)));
+        let time = parse_time + start.elapsed();
+
+        report(PartRun {
+            part,
+            answer: result.map_err(|payload| panic_failure(&*payload)),
+            time,
+        });
+    }
+}
+
+/// All `parts` failed with the same `failure` after `time`.
+fn failed_parts(parts: &[Part], failure: Failure, time: Duration) -> Vec<PartRun> {
+    parts
+        .iter()
+        .map(|&part| PartRun {
+            part,
+            answer: Err(failure.clone()),
+            time,
+        })
+        .collect()
+}
+
+/// The failure of a part that panicked with `payload`.
+fn panic_failure(payload: &(dyn Any + Send)) -> Failure {
+    let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic payload".to_owned()
+    };
+
+    if msg.starts_with("not implemented") || msg.starts_with("not yet implemented") {
+        Failure::NotImplemented
+    } else {
+        Failure::Panic(msg)
+    }
+}
+
+/// Print the result of a part as a JSON object on one line.
+fn print_json(year: u32, day: u32, run: &PartRun) {
+    let (status, answer, error) = match &run.answer {
+        Ok(Answer::Int(i)) => ("ok", json!(i), None),
+        Ok(answer) => ("ok", json!(answer.to_string()), None),
+        Err(Failure::NotImplemented) => ("not-implemented", Value::Null, None),
+        Err(e) => ("error", Value::Null, Some(e.to_string())),
+    };
+
+    let mut object = json!({
+        "year": year,
+        "day": day,
+        "part": run.part as u8,
+        "status": status,
+        "answer": answer,
+        "time_ms": run.time.as_secs_f64() * 1000.0,
+    });
+    if let Some(error) = error {
+        object["error"] = json!(error);
+    }
+    println!("{object}");
+}
+
+fn print_table(rows: &[(u32, PartRun)]) {
+    let answers = rows
+        .iter()
+        .map(|(_, run)| match &run.answer {
+            Ok(answer) => answer.to_string(),
+            Err(e) => e.to_string(),
+        })
+        .collect::<Vec<_>>();
+    let answer_width = answers
+        .iter()
+        .map(String::len)
+        .chain(["answer".len()])
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "{:>3} | {:>4} | {:<answer_width$} | {:>12}",
+        "day", "part", "answer", "time"
+    );
+    println!(
+        "{:-<3}-+-{:-<4}-+-{:-<answer_width$}-+-{:-<12}",
+        "", "", "", ""
+    );
+    for ((day, run), answer) in rows.iter().zip(answers) {
+        let time = format!("{:.2?}", run.time);
+        println!(
+            "{day:>3} | {:>4} | {answer:<answer_width$} | {time:>12}",
+            run.part as u8
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use std::fs::OpenOptions;
+
+use anyhow::Context;
+
+use crate::input;
+
+/// The template of a new day, `{day}` is replaced with the day number.
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// Create `src/dayN.rs` in the crate of `year` from the template and an empty input file.
+///
+/// The day is registered by the build script of the year crate once the file exists. Existing
+/// files are never overwritten.
+pub fn new_day(year: u32, day: u32) -> anyhow::Result<()> {
+    let crate_dir = crate::repo_dir().join(year.to_string());
+    anyhow::ensure!(
+        crate_dir.join("Cargo.toml").is_file(),
+        "no crate for year {year} at '{}'",
+        crate_dir.display()
+    );
+
+    let source = crate_dir.join("src").join(format!("day{day}.rs"));
+    anyhow::ensure!(!source.exists(), "'{}' already exists", source.display());
+    std::fs::write(&source, DAY_TEMPLATE.replace("{day}", &day.to_string()))
+        .with_context(|| format!("failed to write '{}'", source.display()))?;
+    println!("created {}", source.display());
+
+    let input = input::input_path(year, day, None);
+    if let Some(dir) = input.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create '{}'", dir.display()))?;
+    }
+    match OpenOptions::new().write(true).create_new(true).open(&input) {
+        Ok(_) => println!("created {}", input.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e).with_context(|| format!("failed to create '{}'", input.display())),
+    }
+    Ok(())
+}
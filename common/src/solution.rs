@@ -0,0 +1,122 @@
+use std::any::Any;
+
+use crate::Answer;
+
+/// One of the two parts of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One = 1,
+    Two = 2,
+}
+
+/// The solution to the puzzle of one day.
+///
+/// The input is parsed once with [`Solution::parse`], both parts then work on the same parsed
+/// input.
+///
+/// Constants of a puzzle that differ between the examples and the real input are parameters, they
+/// are fields of the solution with the defaults of the real input. [`params!`](crate::params)
+/// implements [`Solution::params`] and [`Solution::set_param`] for them.
+pub trait Solution: Clone {
+    /// The parsed puzzle input shared by both parts.
+    type Input: 'static;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Answer;
+    fn part2(&self, input: &Self::Input) -> Answer;
+
+    /// The names and current values of all parameters.
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+
+    /// Set the parameter `name` to `value`.
+    fn set_param(&mut self, name: &str, _value: &str) -> anyhow::Result<()> {
+        anyhow::bail!("unknown parameter `{name}`, this day has no parameters")
+    }
+}
+
+/// Implement [`Solution::params`] and [`Solution::set_param`] for the given fields, which are
+/// parsed with [`FromStr`](std::str::FromStr).
+///
+/// ```ignore
+/// impl Solution for Day14 {
+///     aoc_common::params!(width, height);
+///     // ...
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    ($($field:ident),+ $(,)?) => {
+        fn params(&self) -> Vec<(&'static str, String)> {
+            vec![$((stringify!($field), self.$field.to_string())),+]
+        }
+
+        fn set_param(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+            match name {
+                $(
+                    stringify!($field) => {
+                        self.$field = value.parse().map_err(|e| {
+                            anyhow::anyhow!("invalid value '{value}' for parameter `{name}`: {e}")
+                        })?;
+                    }
+                )+
+                _ => anyhow::bail!(
+                    "unknown parameter `{name}`, the parameters are: {}",
+                    [$(stringify!($field)),+].join(", ")
+                ),
+            }
+            Ok(())
+        }
+    };
+}
+
+/// Object safe version of [`Solution`] with a type-erased input, so that days can be dispatched
+/// at runtime.
+///
+/// Solutions are `Send + Sync` so that a day can run on its own thread with a time limit.
+pub trait DynSolution: Send + Sync {
+    fn parse_dyn(&self, data: &str) -> anyhow::Result<Box<dyn Any>>;
+
+    /// Solve `part` on an `input` returned by [`DynSolution::parse_dyn`] of the same solution.
+    fn solve_dyn(&self, input: &dyn Any, part: Part) -> Answer;
+
+    fn params_dyn(&self) -> Vec<(&'static str, String)>;
+
+    /// A copy of this solution with the `(name, value)` `params` set.
+    fn with_params(&self, params: &[(String, String)]) -> anyhow::Result<Box<dyn DynSolution>>;
+}
+
+impl<S: Solution + Send + Sync + 'static> DynSolution for S {
+    fn parse_dyn(&self, data: &str) -> anyhow::Result<Box<dyn Any>> {
+        Ok(Box::new(self.parse(data)?))
+    }
+
+    fn solve_dyn(&self, input: &dyn Any, part: Part) -> Answer {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input of a different solution");
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+
+    fn params_dyn(&self) -> Vec<(&'static str, String)> {
+        self.params()
+    }
+
+    fn with_params(&self, params: &[(String, String)]) -> anyhow::Result<Box<dyn DynSolution>> {
+        let mut solution = self.clone();
+        for (name, value) in params {
+            solution.set_param(name, value)?;
+        }
+        Ok(Box::new(solution))
+    }
+}
+
+/// All implemented days of a year, ordered by day.
+pub type Days = [(u32, &'static dyn DynSolution)];
+
+/// All implemented years with their days, ordered by year.
+pub type Years = [(u32, &'static Days)];
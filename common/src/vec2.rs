@@ -0,0 +1,193 @@
+//! Positions and offsets on a grid.
+
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// The integer types a [`Vec2`] can have as coordinates.
+pub trait Coord:
+    Copy
+    + Ord
+    + Default
+    + Hash
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + TryInto<usize>
+{
+    const ONE: Self;
+
+    /// `self + rhs`, [`None`] on overflow.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// `self - rhs`, [`None`] on overflow, like below zero for the unsigned types.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// The distance between `self` and `other`.
+    fn abs_diff(self, other: Self) -> Self {
+        if self < other {
+            other - self
+        } else {
+            self - other
+        }
+    }
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_coord!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// A position or an offset on a grid, `x` going right and `y` going down.
+///
+/// ```
+/// use aoc_common::vec2::Vec2;
+///
+/// let pos = Vec2(3, 4) + Vec2(0, -1) * 2;
+/// assert_eq!(pos, Vec2(3, 2));
+/// assert_eq!(pos.manhattan(Vec2(0, 0)), 5);
+/// assert_eq!(Vec2(0_u32, 1).neighbors4().count(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+pub struct Vec2<T = i32>(pub T, pub T);
+
+impl<T: Coord> Vec2<T> {
+    pub fn x(&self) -> T {
+        self.0
+    }
+    pub fn y(&self) -> T {
+        self.1
+    }
+
+    /// The distance to `other` moving only horizontally and vertically.
+    pub fn manhattan(self, other: Self) -> T {
+        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
+    }
+
+    /// The distance to `other` moving diagonally as well.
+    pub fn chebyshev(self, other: Self) -> T {
+        self.0.abs_diff(other.0).max(self.1.abs_diff(other.1))
+    }
+
+    /// The index of the position in a `width` by `height` grid stored row by row, [`None`]
+    /// outside the grid.
+    pub fn to_index(self, width: usize, height: usize) -> Option<usize> {
+        let x = self.0.try_into().ok().filter(|&x| x < width)?;
+        let y = self.1.try_into().ok().filter(|&y| y < height)?;
+        Some(x + y * width)
+    }
+
+    /// The up to four orthogonal neighbors, clockwise starting up.
+    ///
+    /// Neighbors that don't fit into `T` are skipped, like the ones below zero of an unsigned
+    /// position.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        self.neighbors([(1, 0), (2, 1), (1, 2), (0, 1)])
+    }
+
+    /// The up to eight neighbors including the diagonal ones, clockwise starting up.
+    ///
+    /// Neighbors that don't fit into `T` are skipped, like the ones below zero of an unsigned
+    /// position.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        self.neighbors([
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+            (0, 0),
+        ])
+    }
+
+    /// The neighbors at `steps`, indices into `[minus one, same, plus one]` for `x` and `y`.
+    fn neighbors<const N: usize>(self, steps: [(usize, usize); N]) -> impl Iterator<Item = Self> {
+        let around = |v: T| [v.checked_sub(T::ONE), Some(v), v.checked_add(T::ONE)];
+        let (xs, ys) = (around(self.0), around(self.1));
+        steps
+            .into_iter()
+            .filter_map(move |(x, y)| Some(Vec2(xs[x]?, ys[y]?)))
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Vec2(x, y)
+    }
+}
+
+impl<T: Coord> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Vec2(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: Coord> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coord> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Vec2(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<T: Coord> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coord> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Vec2(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Vec2(-self.0, -self.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_and_distances() {
+        assert_eq!(Vec2(2, 1).to_index(3, 2), Some(5));
+        assert_eq!(Vec2(3, 1).to_index(3, 2), None);
+        assert_eq!(Vec2(-1_i64, 0).to_index(3, 2), None);
+
+        assert_eq!(Vec2(1, -2).chebyshev(Vec2(-2, 0)), 3);
+        assert_eq!(-Vec2(1, -2) - Vec2(1, 1), Vec2(-2, 1));
+        assert_eq!(Vec2(5_u8, 0).neighbors8().count(), 5);
+    }
+}
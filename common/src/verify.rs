@@ -0,0 +1,216 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Context;
+
+use crate::{
+    input,
+    runner::{self, Failure, PartRun},
+    solution::{Days, Part, Years},
+    Answer,
+};
+
+/// The file with the expected answers of `year`: `<year>/answers.toml` in the repository.
+///
+/// It has a table per day with the answers of the parts as integers or strings:
+/// ```toml
+/// [day18]
+/// part1 = 140
+/// part2 = "6,1"
+/// ```
+pub fn answers_path(year: u32) -> PathBuf {
+    crate::repo_dir()
+        .join(year.to_string())
+        .join("answers.toml")
+}
+
+/// Load the expected answers of `year` as strings, indexed by day and part. A missing file has no
+/// answers.
+fn load_answers(year: u32) -> anyhow::Result<toml::Table> {
+    let path = answers_path(year);
+    if !path.exists() {
+        return Ok(toml::Table::new());
+    }
+
+    let data = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read '{}'", path.display()))?;
+    data.parse()
+        .with_context(|| format!("invalid answers file '{}'", path.display()))
+}
+
+/// The expected answer of `day` and `part`, if it is in `answers`.
+fn expected_answer(answers: &toml::Table, day: u32, part: Part) -> anyhow::Result<Option<String>> {
+    let Some(value) = answers
+        .get(&format!("day{day}"))
+        .and_then(|d| d.get(format!("part{}", part as u8)))
+    else {
+        return Ok(None);
+    };
+
+    match value {
+        toml::Value::Integer(i) => Ok(Some(i.to_string())),
+        toml::Value::String(s) => Ok(Some(s.clone())),
+        v => anyhow::bail!(
+            "answer of day {day} part {} must be an integer or a string, not {v}",
+            part as u8
+        ),
+    }
+}
+
+/// The outcome of verifying one part.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    /// The answer isn't the expected one, or the part has none.
+    Fail(String),
+    /// There is no expected answer to compare with.
+    Missing(String),
+}
+
+/// Compare the `answer` of a part with the `expected` one.
+fn compare(expected: Option<&str>, answer: &Result<Answer, Failure>) -> Outcome {
+    match (expected, answer) {
+        (Some(expected), Ok(answer)) if expected == answer.to_string() => Outcome::Pass,
+        (Some(expected), Ok(answer)) => Outcome::Fail(format!("expected {expected}, got {answer}")),
+        (Some(expected), Err(e)) => Outcome::Fail(format!("expected {expected}, {e}")),
+        (None, Ok(answer)) => Outcome::Missing(format!("got {answer}")),
+        (None, Err(e)) => Outcome::Missing(e.to_string()),
+    }
+}
+
+/// The number of parts that passed and failed, and of the days whose input couldn't be read.
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    unreadable: usize,
+}
+
+/// Run all days of the selected `year` (or of all `years`) and compare their answers with the
+/// expected ones.
+///
+/// Prints pass, fail or missing for every part and fails if any answer doesn't match, if an
+/// input can't be read, or if there was no expected answer to compare with at all. A day that panics or runs longer than
+/// `timeout` fails without stopping the other days.
+pub fn verify(
+    years: &Years,
+    year: Option<u32>,
+    timeout: Option<Duration>,
+    trim: bool,
+) -> anyhow::Result<()> {
+    let selected = years
+        .iter()
+        .filter(|(y, _)| year.is_none_or(|year| year == *y))
+        .collect::<Vec<_>>();
+    if let Some(year) = year {
+        anyhow::ensure!(!selected.is_empty(), "year {year} not implemented");
+    }
+
+    let _quiet = runner::log_panics();
+    let mut tally = Tally::default();
+    for &&(year, days) in &selected {
+        verify_year(year, days, timeout, trim, &mut tally)?;
+    }
+
+    anyhow::ensure!(tally.failed == 0, "{} answers didn't match", tally.failed);
+    anyhow::ensure!(
+        tally.unreadable == 0,
+        "{} inputs couldn't be read",
+        tally.unreadable
+    );
+    anyhow::ensure!(
+        tally.passed > 0,
+        "no answer was verified, add the accepted answers to {}",
+        selected
+            .iter()
+            .map(|(year, _)| format!("'{}'", answers_path(*year).display()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    Ok(())
+}
+
+/// Verify all `days` of `year`, counting the passed and failed parts in `tally`.
+fn verify_year(
+    year: u32,
+    days: &Days,
+    timeout: Option<Duration>,
+    trim: bool,
+    tally: &mut Tally,
+) -> anyhow::Result<()> {
+    let answers = load_answers(year)?;
+
+    for &(day, solution) in days {
+        let file = input::input_path(year, day, None);
+        let data = match input::read_input(&file, trim) {
+            Ok(data) => data,
+            Err(e) if input::is_not_found(&e) => {
+                println!("{year} day {day:>2}: missing input '{}'", file.display());
+                continue;
+            }
+            Err(e) => {
+                tally.unreadable += 1;
+                println!("{year} day {day:>2}: unreadable input, {e:#}");
+                continue;
+            }
+        };
+
+        let runs = runner::run_day_isolated(solution, &[Part::One, Part::Two], data, timeout);
+        for PartRun { part, answer, .. } in runs {
+            let label = format!("{year} day {day:>2} part {}", part as u8);
+            let expected = expected_answer(&answers, day, part)?;
+            match compare(expected.as_deref(), &answer) {
+                Outcome::Pass => {
+                    tally.passed += 1;
+                    println!("{label}: pass");
+                }
+                Outcome::Fail(msg) => {
+                    tally.failed += 1;
+                    println!("{label}: FAIL, {msg}");
+                }
+                Outcome::Missing(msg) => println!("{label}: missing, {msg}"),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_answers() {
+        assert_eq!(compare(Some("140"), &Ok(Answer::Int(140))), Outcome::Pass);
+        assert_eq!(
+            compare(Some("6,1"), &Ok(Answer::Coord(6, 1))),
+            Outcome::Pass
+        );
+        assert_eq!(
+            compare(Some("140"), &Ok(Answer::Int(14))),
+            Outcome::Fail("expected 140, got 14".into())
+        );
+        assert_eq!(
+            compare(Some("140"), &Err(Failure::NotImplemented)),
+            Outcome::Fail("expected 140, not implemented".into())
+        );
+        assert_eq!(
+            compare(None, &Ok(Answer::Str("abc".into()))),
+            Outcome::Missing("got abc".into())
+        );
+        assert_eq!(
+            compare(None, &Err(Failure::Panic("oops".into()))),
+            Outcome::Missing("panicked: oops".into())
+        );
+    }
+
+    #[test]
+    fn expected_answers() {
+        let answers = "[day18]\npart1 = 140\npart2 = \"6,1\"\n[day19]\npart1 = 1.5\n";
+        let answers = answers.parse::<toml::Table>().unwrap();
+        let expected = |day, part| expected_answer(&answers, day, part);
+        assert_eq!(expected(18, Part::One).unwrap().as_deref(), Some("140"));
+        assert_eq!(expected(18, Part::Two).unwrap().as_deref(), Some("6,1"));
+        assert_eq!(expected(19, Part::Two).unwrap(), None);
+        assert!(expected(19, Part::One).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! Re-running a day whenever its input file changes, using inotify.
+
+use std::{ffi::OsStr, io::Write, path::Path, thread, time::Duration};
+
+use anyhow::Context;
+use inotify::{Inotify, WatchMask};
+
+use crate::{
+    input,
+    runner::{self, Failure},
+    solution::{DynSolution, Part},
+};
+
+/// How long to wait for the rest of the events of a save before running again, editors often
+/// write a file in several steps.
+const SETTLE_TIME: Duration = Duration::from_millis(50);
+
+/// Run `parts` of `solution` on the input at `path`, normalized with `trim`, and again every time
+/// it changes, until the process is killed.
+///
+/// The screen is cleared before every run. A missing input, a failing parse or a panic is shown
+/// in place of the answers and doesn't stop watching.
+pub fn watch(
+    solution: &dyn DynSolution,
+    parts: &[Part],
+    path: &Path,
+    trim: bool,
+) -> anyhow::Result<()> {
+    let name = path
+        .file_name()
+        .with_context(|| format!("'{}' is not a file", path.display()))?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    // The directory is watched rather than the file, as editors often save by replacing the file
+    // with a new one, which would end a watch of the file itself.
+    let mut inotify = Inotify::init().context("failed to initialize inotify")?;
+    inotify
+        .watches()
+        .add(dir, WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO)
+        .with_context(|| format!("failed to watch '{}'", dir.display()))?;
+
+    let _quiet = runner::log_panics();
+    let mut buffer = [0; 4096];
+    loop {
+        run(solution, parts, path, trim);
+
+        wait_for_change(&mut inotify, &mut buffer, name)?;
+        thread::sleep(SETTLE_TIME);
+        while inotify.read_events(&mut buffer).is_ok() {}
+    }
+}
+
+/// Block until the file `name` in the watched directory is written or replaced.
+fn wait_for_change(inotify: &mut Inotify, buffer: &mut [u8], name: &OsStr) -> anyhow::Result<()> {
+    loop {
+        let mut events = inotify
+            .read_events_blocking(buffer)
+            .context("failed to read inotify events")?;
+        if events.any(|event| event.name == Some(name)) {
+            return Ok(());
+        }
+    }
+}
+
+/// Clear the screen and print the answers of `parts` on the input at `path`.
+fn run(solution: &dyn DynSolution, parts: &[Part], path: &Path, trim: bool) {
+    print!("\x1b[2J\x1b[H");
+    println!("watching '{}', press Ctrl-C to exit\n", path.display());
+
+    match input::read_input(path, trim) {
+        Ok(data) => {
+            let runs = runner::run_day(solution, parts, &data);
+            // A failed parse fails every part, show it only once.
+            if let Some(Err(e @ Failure::Parse { snippet, .. })) =
+                runs.first().map(|run| &run.answer)
+            {
+                println!("{e}");
+                if let Some(snippet) = snippet {
+                    println!("{snippet}");
+                }
+            } else {
+                for run in runs {
+                    match run.answer {
+                        Ok(answer) => println!("part {} = {answer}", run.part as u8),
+                        Err(e) => println!("part {} failed: {e}", run.part as u8),
+                    }
+                }
+            }
+        }
+        Err(e) => println!("{e:#}"),
+    }
+    _ = std::io::stdout().flush();
+}
@@ -0,0 +1,33 @@
+use aoc_common::{Answer, Solution};
+
+#[derive(Clone)]
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Vec<String>;
+
+    fn parse(&self, data: &str) -> anyhow::Result<Self::Input> {
+        Ok(data
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(str::to_owned)
+            .collect())
+    }
+
+    fn part1(&self, _input: &Self::Input) -> Answer {
+        unimplemented!("part 1")
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Answer {
+        unimplemented!("part 2")
+    }
+}
+
+#[test]
+#[ignore = "fill in the example"]
+fn test_p1() {
+    let s = "\
+    ";
+
+    assert_eq!(Answer::Int(0), Day{day}.part1(&Day{day}.parse(s).unwrap()));
+}